@@ -1,6 +1,11 @@
 c_str_consts! {
+    // Set by KISS-UI, and stored by IUP like any other attribute
+    MNEMONIC_TARGET = "KISS_MNEMONIC_TARGET",
+
     //Globals
     UTF8_MODE = "UTF8MODE",
+    DEFAULT_FONT = "DEFAULTFONT",
+    DEFAULT_DECIMAL_SYMBOL = "DEFAULTDECIMALSYMBOL",
 
     // Basic widget attributes
     TITLE = "TITLE",
@@ -8,16 +13,71 @@ c_str_consts! {
     ACTIVE = "ACTIVE",
     NAME = "NAME",
     VISIBLE = "VISIBLE",
+    CANFOCUS = "CANFOCUS",
 
     // Rendering attributes
     RASTERSIZE = "RASTERSIZE",
+    SIZE = "SIZE",
+    USERSIZE = "USERSIZE",
+    MINSIZE = "MINSIZE",
+    MAXSIZE = "MAXSIZE",
+    EXPAND = "EXPAND",
+    PADDING = "PADDING",
     POSITION = "POSITION",
+    NATURALSIZE = "NATURALSIZE",
+
+    // Font attributes
+    FONT = "FONT",
+    CHARSIZE = "CHARSIZE",
+
+    // Color attributes
+    FG_COLOR = "FGCOLOR",
+    BG_COLOR = "BGCOLOR",
+
+    // Tooltip attributes
+    TIP = "TIP",
+    TIP_BALLOON = "TIPBALLOON",
+    TIP_BALLOON_TITLE = "TIPBALLOONTITLE",
+    TIP_BALLOON_TITLE_ICON = "TIPBALLOONTITLEICON",
+    TIP_DELAY = "TIPDELAY",
+    TIP_VISIBLE = "TIPVISIBLE",
+
+    // Canvas attributes
+    BORDER = "BORDER",
+
+    // Dialog attributes
+    DEFAULT_ENTER = "DEFAULTENTER",
+    DEFAULT_ESC = "DEFAULTESC",
+    MENU = "MENU",
+
+    // Drag and drop attributes
+    DROPFILESTARGET = "DROPFILESTARGET",
+    DRAGSOURCE = "DRAGSOURCE",
+    DRAGSOURCEMOVE = "DRAGSOURCEMOVE",
+    DRAGTYPES = "DRAGTYPES",
+    DROPTARGET = "DROPTARGET",
+    DROPTYPES = "DROPTYPES",
+
+    // Clipboard attributes
+    TEXT = "TEXT",
+    TEXT_AVAILABLE = "TEXTAVAILABLE",
+    NATIVE_IMAGE = "NATIVEIMAGE",
+    IMAGE_AVAILABLE = "IMAGEAVAILABLE",
+    FORMAT = "FORMAT",
+    FORMAT_AVAILABLE = "FORMATAVAILABLE",
+    FORMAT_DATA = "FORMATDATA",
+    FORMAT_DATA_SIZE = "FORMATDATASIZE",
 
     // Layout attributes
     ALIGNMENT_VERT = "ALIGNMENTLIN",
     ALIGNMENT_HORI = "ALIGNMENTCOL",
     ORIENTATION = "ORIENTATION",
     NUMDIV = "numdiv",
+    MARGIN = "MARGIN",
+    HOMOGENEOUS = "HOMOGENEOUS",
+    HOMOGENEOUS_COL = "HOMOGENEOUSCOL",
+    HOMOGENEOUS_LIN = "HOMOGENEOUSLIN",
+    EXPAND_CHILDREN = "EXPANDCHILDREN",
     
     // Specific to `Absolute`
     CX = "CX",
@@ -27,6 +87,58 @@ c_str_consts! {
     MULTILINE = "MULTILINE",
     VISIBLE_COLUMNS = "VISIBLECOLUMNS",
     VISIBLE_LINES = "VISIBLELINES",
+    READONLY = "READONLY",
+    PASSWORD = "PASSWORD",
+    NC = "NC",
+    SELECTIONPOS = "SELECTIONPOS",
+    CARETPOS = "CARETPOS",
+    INSERT = "INSERT",
+    APPEND = "APPEND",
+    APPENDNEWLINE = "APPENDNEWLINE",
+    SCROLLTOPOS = "SCROLLTOPOS",
+    LINECOUNT = "LINECOUNT",
+    MASK = "MASK",
+    FORMATTING = "FORMATTING",
+    ADDFORMATTAG_HANDLE = "ADDFORMATTAG_HANDLE",
+    REMOVEFORMATTING = "REMOVEFORMATTING",
+
+    // Text format tag attributes
+    FONTFACE = "FONTFACE",
+    FONTSIZE = "FONTSIZE",
+    WEIGHT = "WEIGHT",
+    ITALIC = "ITALIC",
+    UNDERLINE = "UNDERLINE",
+    STRIKEOUT = "STRIKEOUT",
+    ALIGNMENT = "ALIGNMENT",
+    INDENT = "INDENT",
+    SPIN = "SPIN",
+    SPINMIN = "SPINMIN",
+    SPINMAX = "SPINMAX",
+    SPININC = "SPININC",
+    SPINVALUE = "SPINVALUE",
+    SPINAUTO = "SPINAUTO",
+
+    // Label attributes
+    WORDWRAP = "WORDWRAP",
+    ELLIPSIS = "ELLIPSIS",
+    MARKUP = "MARKUP",
+    SEPARATOR = "SEPARATOR",
+
+    // Link attributes
+    URL = "URL",
+
+    // Button attributes
+    IMPRESS = "IMPRESS",
+    IMINACTIVE = "IMINACTIVE",
+    IMAGEHIGHLIGHT = "IMAGEHIGHLIGHT",
+    IMAGEPOSITION = "IMAGEPOSITION",
+    FLAT = "FLAT",
+    SPACING = "SPACING",
+
+    // Toggle attributes
+    THREE_STATE = "3STATE",
+    SWITCH = "SWITCH",
+    VALUE_HANDLE = "VALUE_HANDLE",
 
     // Progressbar attributes
     DASHED = "DASHED",
@@ -34,6 +146,12 @@ c_str_consts! {
     MIN = "MIN",
     MAX = "MAX",
 
+    // Slider attributes
+    STEP = "STEP",
+    PAGESTEP = "PAGESTEP",
+    SHOWTICKS = "SHOWTICKS",
+    INVERTED = "INVERTED",
+
     //Timer attribute
     TIME = "TIME",
     RUN = "RUN",
@@ -44,11 +162,53 @@ c_str_consts! {
     // Handles
     IMAGE = "IMAGE",
 
+    // Lists
+    DROPDOWN = "DROPDOWN",
+    EDITBOX = "EDITBOX",
+    MULTIPLE = "MULTIPLE",
+    APPENDITEM = "APPENDITEM",
+    INSERTITEM = "INSERTITEM",
+    REMOVEITEM = "REMOVEITEM",
+    SHOWIMAGE = "SHOWIMAGE",
+
+    // Canvas scrolling and drawing
+    SCROLLBAR = "SCROLLBAR",
+    YMAX = "YMAX",
+    DY = "DY",
+    POSY = "POSY",
+    DRAWCOLOR = "DRAWCOLOR",
+    DRAWSTYLE = "DRAWSTYLE",
+    CLIPRECT = "CLIPRECT",
+
     //Callbacks
     ACTION = "ACTION",
     ACTION_CB = "ACTION_CB",
     VALUE_CHANGED_CB = "VALUECHANGED_CB",
     MAP_CB = "MAP_CB", 
+    TIP_CB = "TIP_CB",
+    K_ANY = "K_ANY",
+    KEYPRESS_CB = "KEYPRESS_CB",
+    BUTTON_CB = "BUTTON_CB",
+    MOTION_CB = "MOTION_CB",
+    WHEEL_CB = "WHEEL_CB",
+    ENTERWINDOW_CB = "ENTERWINDOW_CB",
+    LEAVEWINDOW_CB = "LEAVEWINDOW_CB",
+    GETFOCUS_CB = "GETFOCUS_CB",
+    KILLFOCUS_CB = "KILLFOCUS_CB",
+    K_TAB = "K_TAB",
+    K_STAB = "K_sTAB",
+    DROPFILES_CB = "DROPFILES_CB",
+    DRAGBEGIN_CB = "DRAGBEGIN_CB",
+    DRAGDATASIZE_CB = "DRAGDATASIZE_CB",
+    DRAGDATA_CB = "DRAGDATA_CB",
+    DRAGEND_CB = "DRAGEND_CB",
+    DROPDATA_CB = "DROPDATA_CB",
+    DBLCLICK_CB = "DBLCLICK_CB",
+    RESIZE_CB = "RESIZE_CB",
+    SPIN_CB = "SPIN_CB",
+    VALUECHANGING_CB = "VALUECHANGING_CB",
+    CARET_CB = "CARET_CB",
+    LDESTROY_CB = "LDESTROY_CB",
 }
 
 pub mod values {
@@ -2,7 +2,15 @@
 
 use widget_prelude::*;
 
+use callback::{Callback, CallbackStatus};
+use image::{Image, ImageContainer, ImagePosition};
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::marker::PhantomData;
 use std::ptr;
+use std::rc::Rc;
 
 /// A button that can be clicked momentarily and invoke a callback when this happens.
 pub struct Button(IUPPtr);
@@ -17,14 +25,329 @@ impl Button {
     }
 
     /// Set the label of this button. Can be blank.
+    ///
+    /// A `&` before a character makes it the button's mnemonic: it will be underlined, and
+    /// pressing Alt and that character will click the button. Use `&&` for a literal `&`.
     pub fn set_label<L: Into<String>>(self, label: L) -> Self {
         self.set_str_attribute(::attrs::TITLE, label);
         self        
     }
+
+    /// Set the image shown while the mouse is over this button.
+    ///
+    /// Only has an effect if an image was set with `ImageContainer::set_image()`.
+    pub fn set_highlight_image(self, image: Image) -> Self {
+        self.set_attr_handle(::attrs::IMAGEHIGHLIGHT, image);
+        self
+    }
+
+    /// Set the position of this button's image relative to its label. Defaults to
+    /// `ImagePosition::Left`.
+    pub fn set_image_position(self, position: ImagePosition) -> Self {
+        self.set_const_str_attribute(::attrs::IMAGEPOSITION, position.as_cstr());
+        self
+    }
+
+    /// Set the space between this button's image and its label, in pixels.
+    pub fn set_spacing(self, spacing: u32) -> Self {
+        self.set_int_attribute(::attrs::SPACING, spacing as i32);
+        self
+    }
 }
 
 impl_widget! { Button, "button" }
 
+impl_on_tooltip! { Button }
+
+impl_on_key! { Button }
+
+impl_on_focus! { Button }
+
+impl_on_mouse_button! { Button }
+
+impl_on_mouse_enter_leave! { Button }
+
 impl_onclick! { Button }
 
-impl ::image::ImageContainer for Button {}
+impl ImageContainer for Button {}
+
+impl ButtonStyle for Button {}
+
+impl ::container::Padding for Button {}
+
+/// Styling shared by image buttons, such as those in a toolbar.
+///
+/// The images set here are only used if an image was set with `ImageContainer::set_image()`.
+pub trait ButtonStyle: ImageContainer {
+    /// Set the image shown while this button is pressed, or while a toggle is checked.
+    fn set_pressed_image(self, image: Image) -> Self {
+        self.set_attr_handle(::attrs::IMPRESS, image);
+        self
+    }
+
+    /// Set the image shown while this button is disabled.
+    ///
+    /// If not set, a grayed-out version of the image is shown.
+    fn set_inactive_image(self, image: Image) -> Self {
+        self.set_attr_handle(::attrs::IMINACTIVE, image);
+        self
+    }
+
+    /// Set if this button's border should be hidden until the mouse is over it, as in a toolbar.
+    fn set_flat(self, flat: bool) -> Self {
+        self.set_bool_attribute(::attrs::FLAT, flat);
+        self
+    }
+}
+
+/// The state of a `Toggle`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ToggleState {
+    /// Unchecked.
+    Off,
+    /// Checked.
+    On,
+    /// Neither checked nor unchecked, e.g. for a "select all" toggle where only some items are
+    /// selected. Only available if `Toggle::set_three_state(true)` was called.
+    Indeterminate,
+}
+
+impl ToggleState {
+    fn as_cstr(self) -> &'static str {
+        use self::ToggleState::*;
+
+        match self {
+            Off => cstr!("OFF"),
+            On => cstr!("ON"),
+            Indeterminate => cstr!("NOTDEF"),
+        }
+    }
+
+    fn from_value(state: &str) -> ToggleState {
+        match state {
+            "ON" => ToggleState::On,
+            "NOTDEF" => ToggleState::Indeterminate,
+            _ => ToggleState::Off,
+        }
+    }
+}
+
+impl From<bool> for ToggleState {
+    fn from(checked: bool) -> ToggleState {
+        if checked { ToggleState::On } else { ToggleState::Off }
+    }
+}
+
+/// A button that stays checked or unchecked when clicked, rendered as a checkbox by default.
+///
+/// For more info, see the [`IupToggle`][iup-toggle] documentation.
+///
+/// [iup-toggle]: http://webserver2.tecgraf.puc-rio.br/iup/en/elem/iuptoggle.html
+pub struct Toggle(IUPPtr);
+
+impl Default for Toggle {
+    fn default() -> Toggle {
+        Toggle::new()
+    }
+}
+
+impl Toggle {
+    /// Create a new, unchecked `Toggle` with no label.
+    pub fn new() -> Toggle {
+        unsafe {
+            let ptr = ::iup_sys::IupToggle(ptr::null(), ptr::null());
+            Self::from_ptr(ptr)
+        }
+    }
+
+    /// Set the label of this toggle. Can be blank.
+    ///
+    /// A `&` before a character makes it the toggle's mnemonic, as with `Button::set_label()`.
+    pub fn set_label<L: Into<String>>(self, label: L) -> Self {
+        self.set_str_attribute(::attrs::TITLE, label);
+        self
+    }
+
+    /// Set the state of this toggle. Accepts a `bool` for checked or unchecked.
+    ///
+    /// Does not invoke the `OnValueChange` callback.
+    pub fn set_state<S: Into<ToggleState>>(self, state: S) -> Self {
+        self.set_const_str_attribute(::attrs::VALUE, state.into().as_cstr());
+        self
+    }
+
+    /// Get the current state of this toggle.
+    pub fn get_state(&self) -> ToggleState {
+        self.get_str_attribute(::attrs::VALUE)
+            .map_or(ToggleState::Off, |state| ToggleState::from_value(&state))
+    }
+
+    /// Returns `true` if this toggle is checked.
+    pub fn is_checked(&self) -> bool {
+        self.get_state() == ToggleState::On
+    }
+
+    /// Set if this toggle can be put in the `ToggleState::Indeterminate` state, by the user or
+    /// `set_state()`.
+    ///
+    /// Has no effect on image toggles.
+    pub fn set_three_state(self, three_state: bool) -> Self {
+        self.set_bool_attribute(::attrs::THREE_STATE, three_state);
+        self
+    }
+
+    /// Set if this toggle should render as an on/off switch instead of a checkbox.
+    ///
+    /// Falls back to a checkbox on platforms which do not support switches.
+    pub fn set_switch(self, switch: bool) -> Self {
+        self.set_bool_attribute(::attrs::SWITCH, switch);
+        self
+    }
+}
+
+impl_widget! { Toggle, "toggle" }
+
+impl_on_value_change! { Toggle }
+
+impl_on_tooltip! { Toggle }
+
+impl_on_key! { Toggle }
+
+impl_on_focus! { Toggle }
+
+impl_on_mouse_enter_leave! { Toggle }
+
+/// Setting an image renders the toggle as a button which stays pressed while it is checked.
+impl ImageContainer for Toggle {}
+
+impl ButtonStyle for Toggle {}
+
+/// Only has an effect on image toggles.
+impl ::container::Padding for Toggle {}
+
+/// A builder for `RadioGroup`, used to associate its toggles with values.
+pub struct RadioOptions<T> {
+    options: Vec<(IUPPtr, T)>,
+}
+
+impl<T> RadioOptions<T> {
+    /// Add `toggle` as the option for `value`, returning it so it can be placed in a container.
+    pub fn add(&mut self, value: T, toggle: Toggle) -> Toggle {
+        self.options.push((toggle.ptr(), value));
+        toggle
+    }
+}
+
+/// A group of toggles of which only one can be checked at a time, each associated with a value
+/// of type `T`.
+///
+/// For more info, see the [`IupRadio`][iup-radio] documentation.
+///
+/// [iup-radio]: http://webserver2.tecgraf.puc-rio.br/iup/en/elem/iupradio.html
+pub struct RadioGroup<T>(IUPPtr, PhantomData<T>);
+
+impl<T: 'static> RadioGroup<T> {
+    /// Create a new radio group using the given closure, which will be passed a mutable builder
+    /// instance and should return the layout of the group's toggles, e.g. a `Vertical` or
+    /// `Horizontal` container.
+    ///
+    /// Each toggle should be passed through `RadioOptions::add()` with its value as it is
+    /// placed in the layout. The first toggle added is checked initially.
+    pub fn new<F, W>(build_fn: F) -> RadioGroup<T>
+    where F: FnOnce(&mut RadioOptions<T>) -> W, W: Widget {
+        let mut builder = RadioOptions { options: Vec::new() };
+        let layout = build_fn(&mut builder);
+
+        let group = unsafe {
+            let ptr = ::iup_sys::IupRadio(layout.ptr());
+            Self::from_ptr(ptr)
+        };
+
+        // IUP checks the first toggle in the layout, which may not be the first one added.
+        if let Some(&(first, _)) = builder.options.first() {
+            group.set_attr_handle(::attrs::VALUE_HANDLE, unsafe { Toggle::from_ptr(first) });
+        }
+
+        RADIO_OPTIONS.with(|options|
+            options.borrow_mut().insert(group.ptr(), Box::new(builder.options))
+        );
+        ::KISSContext::track_state(group.ptr(), forget_state);
+
+        group
+    }
+
+    fn with_options<F, R>(&self, f: F) -> R where F: FnOnce(&[(IUPPtr, T)]) -> R {
+        RADIO_OPTIONS.with(|options| {
+            let options = options.borrow();
+            let options = options.get(&self.ptr())
+                .and_then(|options| options.downcast_ref::<Vec<(IUPPtr, T)>>())
+                .expect("Radio group options were not registered!");
+
+            f(options)
+        })
+    }
+
+    /// Get the value of the checked toggle, if any.
+    pub fn selected(&self) -> Option<T> where T: Clone {
+        let checked = self.get_attr_handle(::attrs::VALUE_HANDLE)?;
+
+        self.with_options(|options|
+            options.iter()
+                .find(|&&(toggle, _)| toggle == checked.ptr())
+                .map(|(_, value)| value.clone())
+        )
+    }
+
+    /// Check the toggle associated with `value`, unchecking the others.
+    ///
+    /// Does nothing if no toggle is associated with `value`. Does not invoke the change callback.
+    pub fn select(self, value: &T) -> Self where T: PartialEq {
+        let toggle = self.with_options(|options|
+            options.iter().find(|&(_, option)| option == value).map(|&(toggle, _)| toggle)
+        );
+
+        if let Some(toggle) = toggle {
+            self.set_attr_handle(::attrs::VALUE_HANDLE, unsafe { Toggle::from_ptr(toggle) });
+        }
+
+        self
+    }
+
+    /// Set a callback to be invoked with the newly selected value when the user checks a
+    /// different toggle in this group.
+    pub fn set_on_selection_changed<Cb>(self, on_changed: Cb) -> Self
+    where Cb: Callback<(Self, T)>, T: Clone {
+        let on_changed = Rc::new(RefCell::new(on_changed));
+        let options: Vec<_> = self.with_options(|options| options.to_vec());
+
+        for (toggle, value) in options {
+            let on_changed = on_changed.clone();
+            let on_toggle = move |(_, checked): (Toggle, bool)| {
+                // Each change also unchecks the previously checked toggle, which is ignored.
+                if checked {
+                    on_changed.borrow_mut().on_callback((self, value.clone()))
+                } else {
+                    CallbackStatus::Default
+                }
+            };
+
+            let toggle = unsafe { Toggle::from_ptr(toggle) };
+            callback_impl! {
+                ::attrs::ACTION, toggle, on_toggle, Toggle, (Toggle, bool),
+                |element, state: ::libc::c_int| (Toggle::from_ptr(element), state == 1)
+            }
+        }
+
+        self
+    }
+}
+
+thread_local!(
+    static RADIO_OPTIONS: RefCell<HashMap<IUPPtr, Box<dyn Any>>> = RefCell::new(HashMap::new())
+);
+
+fn forget_state(widget: Option<IUPPtr>) {
+    RADIO_OPTIONS.with(|options| ::forget_state(options, widget));
+}
+
+impl_widget! { RadioGroup<T> }
@@ -2,10 +2,21 @@
 
 use widget_prelude::*;
 
+use keyboard::KeyEvent;
+use mouse::{MouseButtonEvent, MouseMoveEvent, MouseWheelEvent};
+
+use utils::cstr::AsCStr;
+
 use iup_sys::Ihandle;
 
+use libc::{c_char, c_int};
+
+use std::borrow::Cow;
 use std::cell::RefCell;
 use std::collections::HashMap;
+use std::ffi::CStr;
+use std::path::PathBuf;
+use std::rc::Rc;
 
 /// Set this within a callback to tell the framework if it should close or not.
 ///
@@ -13,7 +24,9 @@ use std::collections::HashMap;
 /// the dialog containing the widget on which the callback was invoked will be closed.
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub enum CallbackStatus {
-    //Ignore,
+    /// If this is returned from a callback that reports user input, such as a key press, the
+    /// input will be discarded instead of being processed by the widget.
+    Ignore,
     /// The default `CallbackStatus`, does nothing when set.
     Default,
     /// If this is set within a callback, then when the callback returns the dialog containing the
@@ -27,16 +40,31 @@ impl CallbackStatus {
         *self = CallbackStatus::Close;
     }
 
+    pub fn ignore(&mut self) {
+        *self = CallbackStatus::Ignore;
+    }
+
     #[doc(hidden)]
     pub fn to_cb_return(self) -> ::libc::c_int {
         use self::CallbackStatus::*;
 
         match self {
+            Ignore => ::iup_sys::IUP_IGNORE,
             Close => ::iup_sys::IUP_CLOSE,
             Default => ::iup_sys::IUP_DEFAULT,
             // _ => unimplemented!(),
         }
     }
+
+    /// As `to_cb_return()`, but for `K_ANY`, where `Default` passes the key on to the parents
+    /// of the widget so that the shortcuts and mnemonics of its dialog still apply.
+    #[doc(hidden)]
+    pub fn to_key_cb_return(self) -> ::libc::c_int {
+        match self {
+            CallbackStatus::Default => ::iup_sys::IUP_CONTINUE,
+            status => status.to_cb_return(),
+        }
+    }
 }
 
 impl From<()> for CallbackStatus {
@@ -61,30 +89,78 @@ impl<Args, Out: Into<CallbackStatus>, F: 'static> Callback<Args> for F where F:
 pub type CallbackMap<T> = RefCell<HashMap<*mut Ihandle, Box<Callback<T>>>>;
 
 macro_rules! callback_impl {
+    // For the `impl_*!` macros, which also accept widget types with type parameters. The statics
+    // below cannot name those, so a callback taking `Self` is registered for `BaseWidget` and
+    // the widget is converted back before it is invoked.
+    (self: $cb_attr:expr, $widget:expr, $callback:expr) => ({
+        let mut callback = $callback;
+        let callback = move |base: ::base::BaseWidget|
+            ::callback::Callback::on_callback(&mut callback, unsafe { Self::from_ptr(base.ptr()) });
+
+        callback_impl! {
+            $cb_attr, $widget, callback, BaseWidget, ::base::BaseWidget,
+            |element| ::base::BaseWidget::from_ptr(element)
+        }
+    });
     ($cb_attr:expr, $base:expr, $callback:expr, $self_ty:ident) => (
+        callback_impl! {
+            $cb_attr, $base, $callback, $self_ty, $self_ty,
+            |element| $self_ty::from_ptr(element)
+        }
+    );
+    // For `K_ANY`, whose return values differ from other callbacks.
+    (key: $cb_attr:expr, $base:expr, $callback:expr, $self_ty:ident, $args_ty:ty,
+        |$element:ident $(, $arg:ident: $arg_ty:ty)*| $args:expr) => (
+        callback_impl! {
+            @to_key_cb_return $cb_attr, $base, $callback, $self_ty, $args_ty,
+            |$element $(, $arg: $arg_ty)*| $args
+        }
+    );
+    // For callbacks that receive more than the element pointer from IUP. `$args` is evaluated
+    // in an `unsafe` block and should produce the `$args_ty` value passed to the callback.
+    ($cb_attr:expr, $base:expr, $callback:expr, $self_ty:ident, $args_ty:ty,
+        |$element:ident $(, $arg:ident: $arg_ty:ty)*| $args:expr) => (
+        callback_impl! {
+            @to_cb_return $cb_attr, $base, $callback, $self_ty, $args_ty,
+            |$element $(, $arg: $arg_ty)*| $args
+        }
+    );
+    (@$to_return:ident $cb_attr:expr, $base:expr, $callback:expr, $self_ty:ident, $args_ty:ty,
+        |$element:ident $(, $arg:ident: $arg_ty:ty)*| $args:expr) => (
         { 
             thread_local!(
-                static CALLBACKS: ::callback::CallbackMap<$self_ty> = 
+                static CALLBACKS: ::callback::CallbackMap<$args_ty> = 
                     ::std::cell::RefCell::new(::std::collections::HashMap::new())
             );
 
-            extern fn extern_callback(element: *mut ::iup_sys::Ihandle) 
+            extern "C" fn extern_callback($element: *mut ::iup_sys::Ihandle $(, $arg: $arg_ty)*)
             -> ::libc::c_int {
                 use ::callback::CallbackStatus;
 
-                let widget = unsafe { $self_ty::from_ptr(element) };
+                let args = unsafe { $args };
 
                 CALLBACKS.with(|callbacks| 
                     callbacks.borrow_mut()
-                        .get_mut(&widget.ptr())
-                        .map(|cb| cb.on_callback(widget))
-                ).unwrap_or(CallbackStatus::Default).to_cb_return()
+                        .get_mut(&$element)
+                        .map(|cb| cb.on_callback(args))
+                ).unwrap_or(CallbackStatus::Default).$to_return()
+            }
+
+            fn forget(widget: Option<::widget_prelude::IUPPtr>) {
+                CALLBACKS.with(|callbacks| ::forget_state(callbacks, widget));
             }
 
             CALLBACKS.with(|callbacks| 
                 callbacks.borrow_mut().insert($base.ptr(), Box::new($callback))
             );
-            $base.set_callback($cb_attr, extern_callback);                
+            ::KISSContext::track_state($base.ptr(), forget);
+
+            // IUP callbacks are registered with the generic `Icallback` signature and are
+            // called with the arguments specific to each callback name.
+            let extern_callback = extern_callback as *const ();
+            $base.set_callback($cb_attr, unsafe {
+                ::std::mem::transmute::<*const (), ::iup_sys::Icallback>(extern_callback)
+            });
         }
     )
 }
@@ -95,10 +171,10 @@ pub trait OnClick: Widget {
 }
 
 macro_rules! impl_onclick {
-    ($self_ty:ident) => (
-        impl $crate::callback::OnClick for $self_ty {
+    ($self_ty:ident $(<$($param:ident),+>)*) => (
+        impl<$($($param: 'static),+)*> $crate::callback::OnClick for $self_ty $(<$($param),+>)* {
             fn set_onclick<Cb>(self, on_click: Cb) -> Self where Cb: ::callback::Callback<Self> {
-                callback_impl! { $crate::attrs::ACTION, self, on_click, $self_ty }
+                callback_impl! { self: $crate::attrs::ACTION, self, on_click }
                 self
             }
         }
@@ -112,10 +188,12 @@ pub trait OnValueChange: Widget {
 }
 
 macro_rules! impl_on_value_change {
-    ($self_ty:ident) => (
-        impl $crate::callback::OnValueChange for $self_ty {
-            fn set_on_value_changed<Cb>(self, on_value_changed: Cb) -> Self where Cb: ::callback::Callback<Self> {
-                callback_impl! { $crate::attrs::VALUE_CHANGED_CB, self, on_value_changed, $self_ty }
+    ($self_ty:ident $(<$($param:ident),+>)*) => (
+        impl<$($($param: 'static),+)*> $crate::callback::OnValueChange
+        for $self_ty $(<$($param),+>)* {
+            fn set_on_value_changed<Cb>(self, on_value_changed: Cb) -> Self
+            where Cb: ::callback::Callback<Self> {
+                callback_impl! { self: $crate::attrs::VALUE_CHANGED_CB, self, on_value_changed }
                 self
             }
         }
@@ -128,12 +206,465 @@ pub trait OnShow: Widget {
 }
 
 macro_rules! impl_on_show {
-    ($self_ty:ident) => (
-        impl ::callback::OnShow for $self_ty {
+    ($self_ty:ident $(<$($param:ident),+>)*) => (
+        impl<$($($param: 'static),+)*> ::callback::OnShow for $self_ty $(<$($param),+>)* {
             fn set_on_show<Cb>(self, on_show: Cb) -> Self where Cb: ::callback::Callback<Self> {
-                callback_impl! { ::attrs::MAP_CB, self, on_show, $self_ty }
+                callback_impl! { self: ::attrs::MAP_CB, self, on_show }
+                self
+            }
+        }
+    )
+}
+
+/// A trait describing a widget that can show a tooltip, and can notify client code just before
+/// it is shown.
+pub trait OnTooltip: Widget {
+    /// Set a callback to be invoked before this widget's tooltip is shown, with the position of
+    /// the mouse relative to the widget.
+    ///
+    /// Calling `Widget::set_tooltip()` within the callback changes the text that will be shown,
+    /// so it can be computed dynamically.
+    fn set_on_tooltip<Cb>(self, on_tooltip: Cb) -> Self where Cb: Callback<(Self, i32, i32)>;
+}
+
+macro_rules! impl_on_tooltip {
+    ($self_ty:ident $(<$($param:ident),+>)*) => (
+        impl<$($($param: 'static),+)*> ::callback::OnTooltip for $self_ty $(<$($param),+>)* {
+            fn set_on_tooltip<Cb>(self, on_tooltip: Cb) -> Self
+            where Cb: ::callback::Callback<(Self, i32, i32)> {
+                use base::BaseWidget;
+
+                let mut on_tooltip = on_tooltip;
+                let on_tooltip = move |(base, x, y): (BaseWidget, i32, i32)| {
+                    let widget = unsafe { Self::from_ptr(base.ptr()) };
+                    ::callback::Callback::on_callback(&mut on_tooltip, (widget, x, y))
+                };
+
+                callback_impl! {
+                    ::attrs::TIP_CB, self, on_tooltip, BaseWidget, (BaseWidget, i32, i32),
+                    |element, x: ::libc::c_int, y: ::libc::c_int|
+                        (BaseWidget::from_ptr(element), x, y)
+                }
+                self
+            }
+        }
+    )
+}
+
+/// A trait describing a widget that can receive keyboard input, and can notify client code when
+/// a key is pressed.
+///
+/// Return `CallbackStatus::Ignore` from either callback to swallow the key so the widget does
+/// not process it.
+pub trait OnKey: Widget {
+    /// Set a callback to be invoked when a key is pressed while this widget has focus.
+    ///
+    /// If the callback returns `CallbackStatus::Default`, the key is passed on to the callbacks
+    /// of the widget's parents, up to its dialog, so that dialog shortcuts still apply while
+    /// this widget has focus, and is then processed by the widget as usual.
+    fn set_on_key<Cb>(self, on_key: Cb) -> Self where Cb: Callback<(Self, KeyEvent)>;
+
+    /// Set a callback to be invoked when a key is pressed (`true`) or released (`false`) while
+    /// this widget has focus.
+    fn set_on_key_press<Cb>(self, on_key_press: Cb) -> Self
+        where Cb: Callback<(Self, KeyEvent, bool)>;
+}
+
+macro_rules! impl_on_key {
+    ($self_ty:ident $(<$($param:ident),+>)*) => (
+        impl<$($($param: 'static),+)*> ::callback::OnKey for $self_ty $(<$($param),+>)* {
+            fn set_on_key<Cb>(self, on_key: Cb) -> Self
+            where Cb: ::callback::Callback<(Self, ::keyboard::KeyEvent)> {
+                use base::BaseWidget;
+
+                let mut on_key = on_key;
+                let on_key = move |(base, key): (BaseWidget, ::keyboard::KeyEvent)| {
+                    let widget = unsafe { Self::from_ptr(base.ptr()) };
+                    ::callback::Callback::on_callback(&mut on_key, (widget, key))
+                };
+
+                callback_impl! {
+                    key: ::attrs::K_ANY, self, on_key, BaseWidget,
+                    (BaseWidget, ::keyboard::KeyEvent),
+                    |element, c: ::libc::c_int|
+                        (BaseWidget::from_ptr(element), ::keyboard::KeyEvent::from_code(c))
+                }
+                self
+            }
+
+            fn set_on_key_press<Cb>(self, on_key_press: Cb) -> Self
+            where Cb: ::callback::Callback<(Self, ::keyboard::KeyEvent, bool)> {
+                use base::BaseWidget;
+
+                use keyboard::KeyEvent;
+
+                let mut on_key_press = on_key_press;
+                let on_key_press = move |(base, key, press): (BaseWidget, KeyEvent, bool)| {
+                    let widget = unsafe { Self::from_ptr(base.ptr()) };
+                    ::callback::Callback::on_callback(&mut on_key_press, (widget, key, press))
+                };
+
+                callback_impl! {
+                    ::attrs::KEYPRESS_CB, self, on_key_press, BaseWidget,
+                    (BaseWidget, ::keyboard::KeyEvent, bool),
+                    |element, c: ::libc::c_int, press: ::libc::c_int|
+                        (BaseWidget::from_ptr(element), ::keyboard::KeyEvent::from_code(c),
+                         press != 0)
+                }
+                self
+            }
+        }
+    )
+}
+
+/// A trait describing a widget that can notify client code when a mouse button is pressed or
+/// released over it.
+pub trait OnMouseButton: Widget {
+    fn set_on_mouse_button<Cb>(self, on_mouse_button: Cb) -> Self
+        where Cb: Callback<(Self, MouseButtonEvent)>;
+}
+
+macro_rules! impl_on_mouse_button {
+    ($self_ty:ident $(<$($param:ident),+>)*) => (
+        impl<$($($param: 'static),+)*> ::callback::OnMouseButton for $self_ty $(<$($param),+>)* {
+            fn set_on_mouse_button<Cb>(self, on_mouse_button: Cb) -> Self
+            where Cb: ::callback::Callback<(Self, ::mouse::MouseButtonEvent)> {
+                use base::BaseWidget;
+
+                let mut on_mouse_button = on_mouse_button;
+                let on_mouse_button = move |(base, event): (BaseWidget, ::mouse::MouseButtonEvent)|
+                {
+                    let widget = unsafe { Self::from_ptr(base.ptr()) };
+                    ::callback::Callback::on_callback(&mut on_mouse_button, (widget, event))
+                };
+
+                callback_impl! {
+                    ::attrs::BUTTON_CB, self, on_mouse_button, BaseWidget,
+                    (BaseWidget, ::mouse::MouseButtonEvent),
+                    |element, button: ::libc::c_int, pressed: ::libc::c_int,
+                        x: ::libc::c_int, y: ::libc::c_int, status: *mut ::libc::c_char| (
+                        BaseWidget::from_ptr(element),
+                        ::mouse::MouseButtonEvent {
+                            // IUP only reports the five buttons in `MouseButton`.
+                            button: ::mouse::MouseButton::from_code(button)
+                                .unwrap_or(::mouse::MouseButton::Left),
+                            pressed: pressed != 0,
+                            x,
+                            y,
+                            status: ::mouse::MouseStatus::from_ptr(status),
+                        }
+                    )
+                }
+                self
+            }
+        }
+    )
+}
+
+/// A trait describing a widget that can notify client code when the mouse moves over it.
+pub trait OnMouseMove: Widget {
+    fn set_on_mouse_move<Cb>(self, on_mouse_move: Cb) -> Self
+        where Cb: Callback<(Self, MouseMoveEvent)>;
+}
+
+macro_rules! impl_on_mouse_move {
+    ($self_ty:ident $(<$($param:ident),+>)*) => (
+        impl<$($($param: 'static),+)*> ::callback::OnMouseMove for $self_ty $(<$($param),+>)* {
+            fn set_on_mouse_move<Cb>(self, on_mouse_move: Cb) -> Self
+            where Cb: ::callback::Callback<(Self, ::mouse::MouseMoveEvent)> {
+                use base::BaseWidget;
+
+                let mut on_mouse_move = on_mouse_move;
+                let on_mouse_move = move |(base, event): (BaseWidget, ::mouse::MouseMoveEvent)| {
+                    let widget = unsafe { Self::from_ptr(base.ptr()) };
+                    ::callback::Callback::on_callback(&mut on_mouse_move, (widget, event))
+                };
+
+                callback_impl! {
+                    ::attrs::MOTION_CB, self, on_mouse_move, BaseWidget,
+                    (BaseWidget, ::mouse::MouseMoveEvent),
+                    |element, x: ::libc::c_int, y: ::libc::c_int, status: *mut ::libc::c_char| (
+                        BaseWidget::from_ptr(element),
+                        ::mouse::MouseMoveEvent {
+                            x,
+                            y,
+                            status: ::mouse::MouseStatus::from_ptr(status),
+                        }
+                    )
+                }
+                self
+            }
+        }
+    )
+}
+
+/// A trait describing a widget that can notify client code when the mouse wheel is rotated over
+/// it.
+pub trait OnMouseWheel: Widget {
+    fn set_on_mouse_wheel<Cb>(self, on_mouse_wheel: Cb) -> Self
+        where Cb: Callback<(Self, MouseWheelEvent)>;
+}
+
+macro_rules! impl_on_mouse_wheel {
+    ($self_ty:ident $(<$($param:ident),+>)*) => (
+        impl<$($($param: 'static),+)*> ::callback::OnMouseWheel for $self_ty $(<$($param),+>)* {
+            fn set_on_mouse_wheel<Cb>(self, on_mouse_wheel: Cb) -> Self
+            where Cb: ::callback::Callback<(Self, ::mouse::MouseWheelEvent)> {
+                use base::BaseWidget;
+
+                let mut on_mouse_wheel = on_mouse_wheel;
+                let on_mouse_wheel = move |(base, event): (BaseWidget, ::mouse::MouseWheelEvent)| {
+                    let widget = unsafe { Self::from_ptr(base.ptr()) };
+                    ::callback::Callback::on_callback(&mut on_mouse_wheel, (widget, event))
+                };
+
+                callback_impl! {
+                    ::attrs::WHEEL_CB, self, on_mouse_wheel, BaseWidget,
+                    (BaseWidget, ::mouse::MouseWheelEvent),
+                    |element, delta: ::libc::c_float, x: ::libc::c_int, y: ::libc::c_int,
+                        status: *mut ::libc::c_char| (
+                        BaseWidget::from_ptr(element),
+                        ::mouse::MouseWheelEvent {
+                            delta,
+                            x,
+                            y,
+                            status: ::mouse::MouseStatus::from_ptr(status),
+                        }
+                    )
+                }
+                self
+            }
+        }
+    )
+}
+
+/// A trait describing a widget that can notify client code when the mouse enters or leaves its
+/// area.
+pub trait OnMouseEnterLeave: Widget {
+    fn set_on_mouse_enter<Cb>(self, on_mouse_enter: Cb) -> Self where Cb: Callback<Self>;
+
+    fn set_on_mouse_leave<Cb>(self, on_mouse_leave: Cb) -> Self where Cb: Callback<Self>;
+}
+
+macro_rules! impl_on_mouse_enter_leave {
+    ($self_ty:ident $(<$($param:ident),+>)*) => (
+        impl<$($($param: 'static),+)*> ::callback::OnMouseEnterLeave
+        for $self_ty $(<$($param),+>)* {
+            fn set_on_mouse_enter<Cb>(self, on_mouse_enter: Cb) -> Self
+            where Cb: ::callback::Callback<Self> {
+                callback_impl! { self: ::attrs::ENTERWINDOW_CB, self, on_mouse_enter }
+                self
+            }
+
+            fn set_on_mouse_leave<Cb>(self, on_mouse_leave: Cb) -> Self
+            where Cb: ::callback::Callback<Self> {
+                callback_impl! { self: ::attrs::LEAVEWINDOW_CB, self, on_mouse_leave }
+                self
+            }
+        }
+    )
+}
+
+/// A trait describing a widget that can receive the keyboard focus, and can notify client code
+/// when it gains or loses it.
+pub trait OnFocus: Widget {
+    fn set_on_focus<Cb>(self, on_focus: Cb) -> Self where Cb: Callback<Self>;
+
+    fn set_on_focus_lost<Cb>(self, on_focus_lost: Cb) -> Self where Cb: Callback<Self>;
+}
+
+macro_rules! impl_on_focus {
+    ($self_ty:ident $(<$($param:ident),+>)*) => (
+        impl<$($($param: 'static),+)*> ::callback::OnFocus for $self_ty $(<$($param),+>)* {
+            fn set_on_focus<Cb>(self, on_focus: Cb) -> Self where Cb: ::callback::Callback<Self> {
+                callback_impl! { self: ::attrs::GETFOCUS_CB, self, on_focus }
+                self
+            }
+
+            fn set_on_focus_lost<Cb>(self, on_focus_lost: Cb) -> Self
+            where Cb: ::callback::Callback<Self> {
+                callback_impl! { self: ::attrs::KILLFOCUS_CB, self, on_focus_lost }
+                self
+            }
+        }
+    )
+}
+
+/// A trait describing a widget that can accept files dropped onto it from the desktop, and can
+/// notify client code when this occurs.
+pub trait OnDropFiles: Widget {
+    /// Set a callback to be invoked with the paths of the files dropped onto this widget, and the
+    /// position of the drop relative to the widget.
+    ///
+    /// Also enables the widget as a drop target for files.
+    fn set_on_drop_files<Cb>(self, on_drop_files: Cb) -> Self
+        where Cb: Callback<(Self, Vec<PathBuf>, i32, i32)>;
+}
+
+macro_rules! impl_on_drop_files {
+    ($self_ty:ident $(<$($param:ident),+>)*) => (
+        impl<$($($param: 'static),+)*> ::callback::OnDropFiles for $self_ty $(<$($param),+>)* {
+            fn set_on_drop_files<Cb>(self, on_drop_files: Cb) -> Self
+            where Cb: ::callback::Callback<(Self, Vec<::std::path::PathBuf>, i32, i32)> {
+                use base::BaseWidget;
+                use std::path::PathBuf;
+
+                let mut on_drop_files = on_drop_files;
+                let on_drop_files = move |(base, paths, x, y): (BaseWidget, Vec<PathBuf>, i32, i32)|
+                {
+                    let widget = unsafe { Self::from_ptr(base.ptr()) };
+                    ::callback::Callback::on_callback(&mut on_drop_files, (widget, paths, x, y))
+                };
+
+                callback_impl! {
+                    ::attrs::DROPFILES_CB, self, on_drop_files, BaseWidget,
+                    (BaseWidget, Vec<PathBuf>, i32, i32),
+                    |element, filename: *const ::libc::c_char, num: ::libc::c_int,
+                        x: ::libc::c_int, y: ::libc::c_int| {
+                        let path = PathBuf::from(
+                            ::std::ffi::CStr::from_ptr(filename).to_string_lossy().into_owned()
+                        );
+
+                        match ::callback::collect_dropped_file(element, path, num) {
+                            Some(paths) => (BaseWidget::from_ptr(element), paths, x, y),
+                            None => return ::iup_sys::IUP_DEFAULT,
+                        }
+                    }
+                }
+
+                self.set_bool_attribute(::attrs::DROPFILESTARGET, true);
                 self
             }
         }
     )
 }
+
+thread_local!(
+    // IUP reports the files of a single drop one at a time, so they are collected here until
+    // the last one arrives.
+    static DROPPED_FILES: RefCell<HashMap<IUPPtr, Vec<PathBuf>>> = RefCell::new(HashMap::new())
+);
+
+/// Add `path` to the files dropped on `widget`, returning all of them if it is the last.
+#[doc(hidden)]
+pub fn collect_dropped_file(widget: IUPPtr, path: PathBuf, num: c_int) -> Option<Vec<PathBuf>> {
+    ::KISSContext::track_state(widget, forget_dropped_files);
+
+    DROPPED_FILES.with(|dropped| {
+        let mut dropped = dropped.borrow_mut();
+        dropped.entry(widget).or_insert_with(Vec::new).push(path);
+
+        // `num` counts down to zero for the last file in the drop.
+        if num == 0 { dropped.remove(&widget) } else { None }
+    })
+}
+
+fn forget_dropped_files(widget: Option<IUPPtr>) {
+    DROPPED_FILES.with(|dropped| ::forget_state(dropped, widget));
+}
+
+/// A change the user is about to make to the text of a widget, passed to `OnEdit` callbacks.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextEdit {
+    /// The character typed by the user, or `None` if text is being pasted or deleted.
+    pub key: Option<char>,
+    /// The text being inserted, empty if text is only being deleted.
+    pub inserted: String,
+    /// The text of the widget after the change, if it is accepted.
+    pub new_value: String,
+}
+
+impl TextEdit {
+    fn new(old_value: &str, new_value: String, key: c_int) -> TextEdit {
+        // The inserted text is what remains of the new value between the common prefix and
+        // suffix. Both are measured in bytes on character boundaries.
+        let prefix = old_value.char_indices().zip(new_value.chars())
+            .find(|&((_, old), new)| old != new)
+            .map_or(::std::cmp::min(old_value.len(), new_value.len()), |((idx, _), _)| idx);
+
+        let suffix = old_value[prefix..].chars().rev().zip(new_value[prefix..].chars().rev())
+            .take_while(|&(old, new)| old == new)
+            .map(|(old, _)| old.len_utf8())
+            .sum::<usize>();
+
+        TextEdit {
+            key: if key == 0 { None } else { ::std::char::from_u32(key as u32) },
+            inserted: new_value[prefix .. new_value.len() - suffix].to_owned(),
+            new_value,
+        }
+    }
+}
+
+/// What should happen to a `TextEdit`. Returned by `OnEdit` callbacks.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EditResult {
+    /// Let the change happen.
+    Accept,
+    /// Discard the change, leaving the text as it was.
+    Reject,
+    /// Insert this character instead of the one typed.
+    ///
+    /// Only valid if `TextEdit::key` is not `None`; otherwise this is the same as `Accept`.
+    Replace(char),
+}
+
+/// A trait describing a widget with editable text, which can notify client code before the text
+/// is changed by the user and let it reject or alter the change.
+///
+/// The callback is invoked for every keystroke, paste and deletion, after the widget's mask,
+/// if any, has accepted it. It is not invoked when the text is set programmatically.
+pub trait OnEdit: Widget {
+    fn set_on_edit<F>(self, on_edit: F) -> Self
+    where F: FnMut(Self, TextEdit) -> EditResult + 'static {
+        let mut on_edit = on_edit;
+        let on_edit = move |ptr, edit| on_edit(unsafe { Self::from_ptr(ptr) }, edit);
+
+        EDIT_CALLBACKS.with(|callbacks|
+            callbacks.borrow_mut().insert(self.ptr(), Rc::new(RefCell::new(Box::new(on_edit))))
+        );
+        ::KISSContext::track_state(self.ptr(), forget_edit_callbacks);
+
+        self.set_callback(::attrs::ACTION, unsafe {
+            ::std::mem::transmute::<*const (), ::iup_sys::Icallback>(edit_action as *const ())
+        });
+        self
+    }
+}
+
+type EditCallback = Rc<RefCell<Box<dyn FnMut(IUPPtr, TextEdit) -> EditResult>>>;
+
+thread_local!(
+    static EDIT_CALLBACKS: RefCell<HashMap<IUPPtr, EditCallback>> = RefCell::new(HashMap::new())
+);
+
+fn forget_edit_callbacks(widget: Option<IUPPtr>) {
+    EDIT_CALLBACKS.with(|callbacks| ::forget_state(callbacks, widget));
+}
+
+extern "C" fn edit_action(element: *mut Ihandle, c: c_int, new_value: *mut c_char) -> c_int {
+    let on_edit = EDIT_CALLBACKS.with(|callbacks| callbacks.borrow().get(&element).cloned());
+
+    let on_edit = match on_edit {
+        Some(on_edit) => on_edit,
+        None => return ::iup_sys::IUP_DEFAULT,
+    };
+
+    let edit = unsafe {
+        let old_value = ::iup_sys::IupGetAttribute(element, ::attrs::VALUE.as_cstr());
+        let old_value = old_value.as_ref()
+            .map_or(Cow::Borrowed(""), |_| CStr::from_ptr(old_value).to_string_lossy());
+        let new_value = new_value.as_ref()
+            .map_or_else(String::new, |_| CStr::from_ptr(new_value).to_string_lossy().into_owned());
+
+        TextEdit::new(&old_value, new_value, c)
+    };
+
+    let key = edit.key;
+    let result = (*on_edit.borrow_mut())(element, edit);
+
+    match (result, key) {
+        (EditResult::Reject, _) => ::iup_sys::IUP_IGNORE,
+        (EditResult::Replace(replacement), Some(_)) => replacement as c_int,
+        _ => ::iup_sys::IUP_DEFAULT,
+    }
+}
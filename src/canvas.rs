@@ -0,0 +1,56 @@
+//! Blank drawing surfaces that can receive raw input.
+
+use widget_prelude::*;
+
+use std::ptr;
+
+/// A blank widget that can receive raw keyboard and mouse input, including the mouse wheel.
+///
+/// For more info, see the [`IupCanvas`][iup-canvas] documentation.
+///
+/// [iup-canvas]: http://webserver2.tecgraf.puc-rio.br/iup/en/elem/iupcanvas.html
+pub struct Canvas(IUPPtr);
+
+impl Default for Canvas {
+    fn default() -> Canvas {
+        Canvas::new()
+    }
+}
+
+impl Canvas {
+    /// Create a new, blank canvas.
+    pub fn new() -> Canvas {
+        unsafe {
+            let ptr = ::iup_sys::IupCanvas(ptr::null());
+            Self::from_ptr(ptr)
+        }
+    }
+
+    /// Set if this canvas should show a border around its area.
+    pub fn set_border(self, border: bool) -> Self {
+        self.set_bool_attribute(::attrs::BORDER, border);
+        self
+    }
+}
+
+impl_widget! { Canvas, "canvas" }
+
+impl_on_tooltip! { Canvas }
+
+impl_on_key! { Canvas }
+
+impl_on_focus! { Canvas }
+
+impl_on_mouse_button! { Canvas }
+
+impl_on_mouse_move! { Canvas }
+
+impl_on_mouse_wheel! { Canvas }
+
+impl_on_mouse_enter_leave! { Canvas }
+
+impl_on_drop_files! { Canvas }
+
+impl ::dragdrop::DragSource for Canvas {}
+
+impl ::dragdrop::DropTarget for Canvas {}
@@ -0,0 +1,158 @@
+//! Access to the system clipboard.
+
+use widget_prelude::*;
+
+use utils::cstr::AsCStr;
+
+use base::BaseWidget;
+use image::Image;
+
+use iup_sys::{self, Ihandle};
+
+use libc::{c_char, c_void};
+
+use std::slice;
+
+extern "C" {
+    // Not bound by `iup-sys`, but part of the IUP API since 3.0.
+    fn IupGetNativeHandleImage(handle: *mut c_void) -> *mut Ihandle;
+}
+
+/// A handle to the system clipboard, for copying and pasting text, images and custom data.
+///
+/// For more info, see the [`IupClipboard`][iup-clipboard] documentation.
+///
+/// ##Note: Not a Renderable Widget
+/// While this type can be dereferenced and converted to `BaseWidget`, it is *not* a renderable
+/// widget and adding it to a container will have no visual effect.
+///
+/// ##Note: Resource Usage
+/// This struct should be freed by calling `.destroy()` on it when it is no longer in use.
+/// Otherwise, it will be freed when `kiss_ui::show_gui()` returns. The clipboard contents are
+/// unaffected by this.
+///
+/// [iup-clipboard]: http://webserver2.tecgraf.puc-rio.br/iup/en/elem/iupclipboard.html
+pub struct Clipboard(IUPPtr);
+
+impl Default for Clipboard {
+    fn default() -> Clipboard {
+        Clipboard::new()
+    }
+}
+
+impl Clipboard {
+    /// Get a handle to the system clipboard.
+    pub fn new() -> Clipboard {
+        unsafe {
+            let ptr = iup_sys::IupClipboard();
+            Self::from_ptr(ptr)
+        }
+    }
+
+    /// Copy `text` to the clipboard, replacing its contents.
+    pub fn set_text(self, text: &str) -> Self {
+        self.set_str_attribute(::attrs::TEXT, text);
+        self
+    }
+
+    /// Get the text on the clipboard, if any.
+    pub fn get_text(&self) -> Option<String> {
+        self.get_str_attribute(::attrs::TEXT).map(|text| text.to_string())
+    }
+
+    /// Returns `true` if there is text on the clipboard.
+    pub fn has_text(self) -> bool {
+        self.get_int_attribute(::attrs::TEXT_AVAILABLE) != 0
+    }
+
+    /// Copy `image` to the clipboard, replacing its contents.
+    ///
+    /// The clipboard keeps its own copy, so `image` may be destroyed afterwards.
+    pub fn set_image(self, image: Image) -> Self {
+        self.set_attr_handle(::attrs::IMAGE, image);
+        self
+    }
+
+    /// Get the image on the clipboard, if any, copying it into a new `Image`.
+    ///
+    /// The returned image should be destroyed when it is no longer in use.
+    pub fn get_image(&self) -> Option<Image> {
+        unsafe {
+            let native = iup_sys::IupGetAttribute(self.ptr(), ::attrs::NATIVE_IMAGE.as_cstr());
+
+            if native.is_null() {
+                return None;
+            }
+
+            let ptr = IupGetNativeHandleImage(native as *mut c_void);
+
+            // The image is created for us, so it must not leak if it is not an `Image`.
+            BaseWidget::from_ptr_opt(ptr).and_then(|base|
+                base.try_downcast().map_err(|base: BaseWidget| iup_sys::IupDestroy(base.ptr())).ok()
+            )
+        }
+    }
+
+    /// Returns `true` if there is an image on the clipboard.
+    pub fn has_image(self) -> bool {
+        self.get_int_attribute(::attrs::IMAGE_AVAILABLE) != 0
+    }
+
+    /// Register a custom data format with the system, so it can be used with `set_data()` and
+    /// `get_data()`.
+    ///
+    /// Formats should be registered once, before they are used. Other applications can exchange
+    /// data in the same format by registering the same name.
+    pub fn register_format(self, format: &str) -> Self {
+        self.set_str_attribute(::attrs::FORMAT, format);
+        self
+    }
+
+    /// Copy `data` to the clipboard in the custom `format`, replacing its contents.
+    pub fn set_data(self, format: &str, data: &[u8]) -> Self {
+        self.set_str_attribute(::attrs::FORMAT, format);
+        self.set_int_attribute(::attrs::FORMAT_DATA_SIZE, data.len() as i32);
+
+        // IUP copies the data out of the buffer immediately.
+        unsafe {
+            iup_sys::IupSetAttribute(
+                self.ptr(), ::attrs::FORMAT_DATA.as_cstr(), data.as_ptr() as *const c_char
+            );
+        }
+
+        self
+    }
+
+    /// Get the data on the clipboard in the custom `format`, if any.
+    pub fn get_data(&self, format: &str) -> Option<Vec<u8>> {
+        self.set_str_attribute(::attrs::FORMAT, format);
+
+        unsafe {
+            let data = iup_sys::IupGetAttribute(self.ptr(), ::attrs::FORMAT_DATA.as_cstr());
+
+            if data.is_null() {
+                return None;
+            }
+
+            // Only valid after `FORMATDATA` has been read.
+            let size = self.get_int_attribute(::attrs::FORMAT_DATA_SIZE);
+            Some(slice::from_raw_parts(data as *const u8, size as usize).to_owned())
+        }
+    }
+
+    /// Returns `true` if there is data on the clipboard in the custom `format`.
+    pub fn has_format(self, format: &str) -> bool {
+        self.set_str_attribute(::attrs::FORMAT, format);
+        self.get_int_attribute(::attrs::FORMAT_AVAILABLE) != 0
+    }
+
+    /// Clear the contents of the clipboard.
+    pub fn clear(self) -> Self {
+        self.set_opt_str_attribute::<String>(::attrs::TEXT, None);
+        self
+    }
+}
+
+impl_widget! { Clipboard, "clipboard" }
+
+impl Destroy for Clipboard {}
@@ -0,0 +1,181 @@
+//! Colors for rendering widgets.
+//!
+//! IUP describes colors as strings in the form `"r g b"`, with each component in `0-255`.
+//! The `Color` type in this module can be parsed from this syntax or from the `#rrggbb` hex
+//! notation, and is formatted back to IUP's syntax.
+//!
+//! For more info, see the [IUP color attribute][iup-color] documentation.
+//!
+//! [iup-color]: http://webserver2.tecgraf.puc-rio.br/iup/en/attrib/iup_bgcolor.html
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// An RGBA color with 8 bits per component.
+///
+/// The alpha component is only respected by widgets which support transparency; it is omitted
+/// from the formatted string when it is fully opaque.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const BLACK: Color = Color::rgb(0, 0, 0);
+    pub const WHITE: Color = Color::rgb(255, 255, 255);
+    pub const GRAY: Color = Color::rgb(128, 128, 128);
+    pub const RED: Color = Color::rgb(255, 0, 0);
+    pub const GREEN: Color = Color::rgb(0, 255, 0);
+    pub const BLUE: Color = Color::rgb(0, 0, 255);
+    pub const YELLOW: Color = Color::rgb(255, 255, 0);
+    pub const CYAN: Color = Color::rgb(0, 255, 255);
+    pub const MAGENTA: Color = Color::rgb(255, 0, 255);
+    pub const TRANSPARENT: Color = Color::rgba(0, 0, 0, 0);
+
+    /// Create a fully opaque color from its red, green and blue components.
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Color {
+        Color { r, g, b, a: 255 }
+    }
+
+    /// Create a color from its red, green, blue and alpha components.
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Color {
+        Color { r, g, b, a }
+    }
+
+    /// Format this color in `#rrggbb` hex notation, or `#rrggbbaa` if it is not fully opaque.
+    pub fn to_hex(&self) -> String {
+        if self.a == 255 {
+            format!("#{:02x}{:02x}{:02x}", self.r, self.g, self.b)
+        } else {
+            format!("#{:02x}{:02x}{:02x}{:02x}", self.r, self.g, self.b, self.a)
+        }
+    }
+
+    fn from_hex(hex: &str) -> Result<Color, ParseColorError> {
+        if hex.len() != 6 && hex.len() != 8 || !hex.is_ascii() {
+            return Err(ParseColorError::InvalidHex);
+        }
+
+        let component = |idx: usize|
+            u8::from_str_radix(&hex[idx..idx + 2], 16).map_err(|_| ParseColorError::InvalidHex);
+
+        let alpha = if hex.len() == 8 { component(6)? } else { 255 };
+
+        Ok(Color::rgba(component(0)?, component(2)?, component(4)?, alpha))
+    }
+}
+
+impl Display for Color {
+    /// Format this color in IUP's `"r g b"` syntax, or `"r g b a"` if it is not fully opaque.
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(fmt, "{} {} {}", self.r, self.g, self.b)?;
+
+        if self.a != 255 {
+            write!(fmt, " {}", self.a)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    /// Parse a color from IUP's `"r g b"` or `"r g b a"` syntax, or from `#rrggbb` or `#rrggbbaa`
+    /// hex notation.
+    fn from_str(s: &str) -> Result<Color, ParseColorError> {
+        let s = s.trim();
+
+        if let Some(hex) = s.strip_prefix('#') {
+            return Color::from_hex(hex);
+        }
+
+        let components = s.split_whitespace()
+            .map(|comp| comp.parse().map_err(|_| ParseColorError::InvalidComponent(comp.to_owned())))
+            .collect::<Result<Vec<u8>, _>>()?;
+
+        match components[..] {
+            [r, g, b] => Ok(Color::rgb(r, g, b)),
+            [r, g, b, a] => Ok(Color::rgba(r, g, b, a)),
+            _ => Err(ParseColorError::WrongComponentCount(components.len())),
+        }
+    }
+}
+
+impl From<(u8, u8, u8)> for Color {
+    fn from((r, g, b): (u8, u8, u8)) -> Color {
+        Color::rgb(r, g, b)
+    }
+}
+
+impl From<(u8, u8, u8, u8)> for Color {
+    fn from((r, g, b, a): (u8, u8, u8, u8)) -> Color {
+        Color::rgba(r, g, b, a)
+    }
+}
+
+/// An error returned when parsing a `Color` from a string fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseColorError {
+    /// The string after `#` was not 6 or 8 hexadecimal digits.
+    InvalidHex,
+    /// A component was not an integer in `0-255`.
+    InvalidComponent(String),
+    /// There were not 3 or 4 components.
+    WrongComponentCount(usize),
+}
+
+impl Display for ParseColorError {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        use self::ParseColorError::*;
+
+        match *self {
+            InvalidHex => fmt.write_str("expected 6 or 8 hex digits after '#'"),
+            InvalidComponent(ref comp) =>
+                write!(fmt, "expected a color component in 0-255, got {:?}", comp),
+            WrongComponentCount(count) =>
+                write!(fmt, "expected 3 or 4 color components, got {}", count),
+        }
+    }
+}
+
+impl Error for ParseColorError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{Color, ParseColorError};
+
+    #[test]
+    fn parse_iup_syntax() {
+        assert_eq!("255 128 0".parse(), Ok(Color::rgb(255, 128, 0)));
+        assert_eq!(" 1 2 3 4 ".parse(), Ok(Color::rgba(1, 2, 3, 4)));
+
+        assert_eq!("1 2".parse::<Color>(), Err(ParseColorError::WrongComponentCount(2)));
+        assert_eq!(
+            "1 2 256".parse::<Color>(), Err(ParseColorError::InvalidComponent("256".into()))
+        );
+    }
+
+    #[test]
+    fn parse_hex() {
+        assert_eq!("#ff8000".parse(), Ok(Color::rgb(255, 128, 0)));
+        assert_eq!("#FF800080".parse(), Ok(Color::rgba(255, 128, 0, 128)));
+
+        assert_eq!("#ff80".parse::<Color>(), Err(ParseColorError::InvalidHex));
+        assert_eq!("#gg8000".parse::<Color>(), Err(ParseColorError::InvalidHex));
+        assert_eq!("#ff80\u{e9}".parse::<Color>(), Err(ParseColorError::InvalidHex));
+    }
+
+    #[test]
+    fn format() {
+        assert_eq!(Color::rgb(255, 128, 0).to_string(), "255 128 0");
+        assert_eq!(Color::rgba(255, 128, 0, 64).to_string(), "255 128 0 64");
+
+        assert_eq!(Color::rgb(255, 128, 0).to_hex(), "#ff8000");
+        assert_eq!(Color::rgba(255, 128, 0, 64).to_hex(), "#ff800040");
+    }
+}
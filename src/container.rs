@@ -8,7 +8,7 @@
 use base::BaseWidget;
 use widget_prelude::*;
 
-/// Vertical alignment setting, used by `Horizontal` and `Grid`.
+/// Vertical alignment setting, used by `Horizontal`, `Grid` and `Label`.
 #[derive(Copy, Clone)]
 pub enum VAlign {
     Top,
@@ -17,7 +17,8 @@ pub enum VAlign {
 }
 
 impl VAlign {
-    fn as_cstr(self) -> &'static str {
+    #[doc(hidden)]
+    pub fn as_cstr(self) -> &'static str {
         use self::VAlign::*;
 
         match self {
@@ -28,7 +29,7 @@ impl VAlign {
     }
 }
 
-/// Horizontal alignment setting, used by `Vertical` and `Grid`.
+/// Horizontal alignment setting, used by `Vertical`, `Grid` and `Label`.
 #[derive(Copy, Clone)]
 pub enum HAlign {
     Left,
@@ -37,7 +38,8 @@ pub enum HAlign {
 }
 
 impl HAlign {
-    fn as_cstr(self) -> &'static str {
+    #[doc(hidden)]
+    pub fn as_cstr(self) -> &'static str {
         use self::HAlign::*;
 
         match self {
@@ -67,8 +69,57 @@ impl Orientation {
     }
 }
 
+/// How a widget should grow to fill the space available in its container.
+///
+/// Set with `Widget::set_expand()`.
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum Expand {
+    /// Keep the natural size of the widget. **Default** for most widgets.
+    None,
+    /// Grow horizontally to fill the available width.
+    Horizontal,
+    /// Grow vertically to fill the available height.
+    Vertical,
+    /// Grow in both directions. **Default** for containers.
+    Both,
+    /// Like `Horizontal`, but the expansion of this widget does not propagate to its parent.
+    HorizontalFree,
+    /// Like `Vertical`, but the expansion of this widget does not propagate to its parent.
+    VerticalFree,
+}
+
+impl Expand {
+    #[doc(hidden)]
+    pub fn as_cstr(self) -> &'static str {
+        use self::Expand::*;
+
+        match self {
+            None => cstr!("NO"),
+            Horizontal => cstr!("HORIZONTAL"),
+            Vertical => cstr!("VERTICAL"),
+            Both => cstr!("YES"),
+            HorizontalFree => cstr!("HORIZONTALFREE"),
+            VerticalFree => cstr!("VERTICALFREE"),
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn size_str(width: u32, height: u32) -> String {
+    format!("{}x{}", width, height)
+}
+
+/// A widget with space between its edges and its contents, such as a button or label.
+pub trait Padding: Widget {
+    /// Set the space between the edges of this widget and its contents, in pixels.
+    fn set_padding(self, horizontal: u32, vertical: u32) -> Self {
+        self.set_str_attribute(::attrs::PADDING, size_str(horizontal, vertical));
+        self
+    }
+}
 
-fn raw_handle_vec<B>(widgets: B) -> Vec<IUPPtr> where B: AsRef<[BaseWidget]> {
+#[doc(hidden)]
+pub fn raw_handle_vec<B>(widgets: B) -> Vec<IUPPtr> where B: AsRef<[BaseWidget]> {
     let mut raw_handles: Vec<_> = widgets.as_ref().iter().cloned().map(BaseWidget::ptr).collect();
     raw_handles.push(::std::ptr::null_mut());
     raw_handles
@@ -150,6 +201,25 @@ impl Horizontal {
         self.set_str_attribute(::attrs::GAP, spacing.to_string());
         self
     } 
+
+    /// Set the space between the edges of this container and its children, in pixels.
+    pub fn set_margin(self, horizontal: u32, vertical: u32) -> Self {
+        self.set_str_attribute(::attrs::MARGIN, size_str(horizontal, vertical));
+        self
+    }
+
+    /// Set if all children should be given the same width, that of the widest child.
+    pub fn set_homogeneous(self, homogeneous: bool) -> Self {
+        self.set_bool_attribute(::attrs::HOMOGENEOUS, homogeneous);
+        self
+    }
+
+    /// Set if all children should expand to fill the container, regardless of their own
+    /// `Widget::set_expand()` setting.
+    pub fn set_expand_children(self, expand_children: bool) -> Self {
+        self.set_bool_attribute(::attrs::EXPAND_CHILDREN, expand_children);
+        self
+    }
 }
 
 impl_widget! { Horizontal, "hbox" }
@@ -176,6 +246,25 @@ impl Vertical {
         self.set_str_attribute(::attrs::GAP, spacing.to_string());
         self
     }
+
+    /// Set the space between the edges of this container and its children, in pixels.
+    pub fn set_margin(self, horizontal: u32, vertical: u32) -> Self {
+        self.set_str_attribute(::attrs::MARGIN, size_str(horizontal, vertical));
+        self
+    }
+
+    /// Set if all children should be given the same height, that of the tallest child.
+    pub fn set_homogeneous(self, homogeneous: bool) -> Self {
+        self.set_bool_attribute(::attrs::HOMOGENEOUS, homogeneous);
+        self
+    }
+
+    /// Set if all children should expand to fill the container, regardless of their own
+    /// `Widget::set_expand()` setting.
+    pub fn set_expand_children(self, expand_children: bool) -> Self {
+        self.set_bool_attribute(::attrs::EXPAND_CHILDREN, expand_children);
+        self
+    }
 }
 
 
@@ -214,6 +303,27 @@ impl Grid {
         self
     }
 
+    /// Set the space between the edges of this container and its children, in pixels.
+    pub fn set_margin(self, horizontal: u32, vertical: u32) -> Self {
+        self.set_str_attribute(::attrs::MARGIN, size_str(horizontal, vertical));
+        self
+    }
+
+    /// Set if all columns should be given the same width, and all rows the same height, those
+    /// of the widest column and the tallest row.
+    pub fn set_homogeneous(self, columns: bool, rows: bool) -> Self {
+        self.set_bool_attribute(::attrs::HOMOGENEOUS_COL, columns);
+        self.set_bool_attribute(::attrs::HOMOGENEOUS_LIN, rows);
+        self
+    }
+
+    /// Set if all children should expand to fill their cells, regardless of their own
+    /// `Widget::set_expand()` setting.
+    pub fn set_expand_children(self, expand_children: bool) -> Self {
+        self.set_bool_attribute(::attrs::EXPAND_CHILDREN, expand_children);
+        self
+    }
+
     /// Sets how children are distributed in the container.
     ///
     /// * `Vertical`: The container will fill columns first.
@@ -262,6 +372,25 @@ impl Grid {
 
 impl_widget! { Grid, "matrix" }
 
+/// A container widget that fills the area behind its single child with a background color.
+///
+/// To arrange multiple widgets over the same background, use another container as the child.
+pub struct BackgroundBox(IUPPtr);
+
+impl BackgroundBox {
+    /// Create a new background box around `child`.
+    ///
+    /// The fill color is inherited from the parent until set with `Widget::set_bg_color()`.
+    pub fn new<W>(child: W) -> BackgroundBox where W: Widget {
+        unsafe {
+            let ptr = ::iup_sys::IupBackgroundBox(child.ptr());
+            Self::from_ptr(ptr)
+        }
+    }
+}
+
+impl_widget! { BackgroundBox, "backgroundbox" }
+
 /// Convert a heterogeneous list of widgets into a `Vec<BaseWidget>`,
 /// suitable for passing to any function that takes `AsRef<[BaseWidget]>`, such as a constructor
 /// for one of the container types.
@@ -1,12 +1,23 @@
 //! KISS-UI top-level dialogs (windows)
 
 use base::BaseWidget;
+use button::Button;
+use callback::{Callback, CallbackStatus, OnKey};
+use keyboard::{KeyEvent, ParseKeyError};
+use menu::Menu;
 use widget_prelude::*;
 
 use ::iup_sys;
 
+use libc::c_int;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::error::Error;
 use std::ffi::CString;
+use std::fmt::{self, Display, Formatter};
 use std::ptr;
+use std::rc::Rc;
 
 /// A top-level dialog that can create a new native window when shown,
 /// and can contain a single widget (which can be a container for many widgets).
@@ -26,10 +37,13 @@ impl Dialog {
     pub fn new<W>(contents: W) -> Dialog where W: Widget {
         assert_kiss_running!();
 
-        unsafe { 
+        let dialog = unsafe { 
             let ptr = iup_sys::IupDialog(contents.ptr());
             Self::from_ptr(ptr)
-        }
+        };
+
+        install_key_dispatch(dialog);
+        dialog
     }
 
     /// Create a new dialog with no children.
@@ -39,10 +53,13 @@ impl Dialog {
     pub fn empty() -> Dialog {
         assert_kiss_running!();
 
-        unsafe {
+        let dialog = unsafe {
             let ptr = iup_sys::IupDialog(ptr::null_mut());
             Self::from_ptr(ptr)
-        }
+        };
+
+        install_key_dispatch(dialog);
+        dialog
     }
 
     /// Set the title of this dialog, which will appear in the title bar of the native window.
@@ -58,6 +75,116 @@ impl Dialog {
         self
     }
 
+    /// Set the button to be activated when the user presses Enter in this dialog.
+    ///
+    /// Does not apply while a widget that processes Enter itself, such as a multiline text box,
+    /// has the focus.
+    pub fn set_default_enter(self, button: Button) -> Self {
+        self.set_attr_handle(::attrs::DEFAULT_ENTER, button);
+        self
+    }
+
+    /// Set the button to be activated when the user presses Escape in this dialog.
+    ///
+    /// This is typically a "Cancel" or "Close" button.
+    pub fn set_default_esc(self, button: Button) -> Self {
+        self.set_attr_handle(::attrs::DEFAULT_ESC, button);
+        self
+    }
+
+    /// Set the order in which the Tab and Shift+Tab keys move the keyboard focus between
+    /// widgets in this dialog, wrapping around at either end.
+    ///
+    /// By default, the focus moves through widgets in the order they were added to their
+    /// containers. Widgets not in `order` keep the default behavior.
+    ///
+    /// See the `children![]` macro in this crate for building `order`.
+    pub fn set_tab_order<C>(self, order: C) -> Self where C: AsRef<[BaseWidget]> {
+        let order = order.as_ref();
+
+        for (idx, &widget) in order.iter().enumerate() {
+            let next = order[(idx + 1) % order.len()];
+            let prev = order[(idx + order.len() - 1) % order.len()];
+
+            // Ignoring the key stops IUP from moving the focus itself.
+            let focus_next = move |_| { next.set_focus(); CallbackStatus::Ignore };
+            callback_impl! { ::attrs::K_TAB, widget, focus_next, BaseWidget }
+
+            let focus_prev = move |_| { prev.set_focus(); CallbackStatus::Ignore };
+            callback_impl! { ::attrs::K_STAB, widget, focus_prev, BaseWidget }
+        }
+
+        self
+    }
+
+    /// Register a callback to be invoked when `shortcut` is pressed anywhere in this dialog,
+    /// e.g. `"Ctrl+S"` or `"F5"`. See `keyboard::KeyEvent` for the accepted syntax.
+    ///
+    /// The key is swallowed when the shortcut is invoked, so the focused widget does not
+    /// process it as well. The callback may still return `CallbackStatus::Close`.
+    ///
+    /// For a shortcut shown beside a menu item, use `MenuItem::set_shortcut()` instead.
+    ///
+    /// ##Note
+    /// Shortcuts are checked after the `OnKey` callbacks of the focused widget and its parents,
+    /// so one of those can return `CallbackStatus::Ignore` to handle the key itself instead.
+    ///
+    /// Returns an error if `shortcut` cannot be parsed, or if a shortcut for the same keys is
+    /// already registered on this dialog.
+    pub fn add_shortcut<Cb>(self, shortcut: &str, callback: Cb) -> Result<Self, ShortcutError>
+    where Cb: Callback<Dialog> {
+        let shortcut: KeyEvent = shortcut.parse().map_err(ShortcutError::Parse)?;
+
+        with_dialog_keys(self, |keys| {
+            if keys.shortcuts.contains_key(&shortcut) {
+                return Err(ShortcutError::Conflict(shortcut));
+            }
+
+            keys.shortcuts.insert(shortcut, Rc::new(RefCell::new(Box::new(callback))));
+            Ok(())
+        })?;
+
+        Ok(self)
+    }
+
+    /// Set the menu shown in the menu bar of this dialog.
+    ///
+    /// The shortcuts of its items, including those in submenus, are registered as with
+    /// `add_shortcut()`, so they should be set before the menu is. Those of the menu set
+    /// before, if any, are removed.
+    ///
+    /// Returns an error, leaving the current menu in place, if two items have the same
+    /// shortcut, or if a shortcut for the same keys was added with `add_shortcut()`.
+    pub fn set_menu(self, menu: Menu) -> Result<Self, ShortcutError> {
+        let items = menu.shortcuts();
+
+        with_dialog_keys(self, |keys| {
+            for (idx, &(shortcut, _)) in items.iter().enumerate() {
+                let is_registered = keys.shortcuts.contains_key(&shortcut)
+                    && !keys.menu_shortcuts.contains(&shortcut);
+
+                if is_registered || items[..idx].iter().any(|&(other, _)| other == shortcut) {
+                    return Err(ShortcutError::Conflict(shortcut));
+                }
+            }
+
+            for shortcut in keys.menu_shortcuts.drain(..) {
+                keys.shortcuts.remove(&shortcut);
+            }
+
+            for (shortcut, item) in items {
+                let activate = move |_: Dialog| item.activate();
+                keys.shortcuts.insert(shortcut, Rc::new(RefCell::new(Box::new(activate))));
+                keys.menu_shortcuts.push(shortcut);
+            }
+
+            Ok(())
+        })?;
+
+        self.set_attr_handle(::attrs::MENU, menu);
+        Ok(self)
+    }
+
     /// Get a child of this dialog named by `name`.
     ///
     /// Returns `None` if the child was not found.
@@ -75,8 +202,116 @@ impl Destroy for Dialog {}
 
 impl_widget! { Dialog, "dialog" }
 
+impl_on_tooltip! { Dialog }
+
+// `K_ANY` on a dialog is shared between shortcuts, label mnemonics and `OnKey::set_on_key()`,
+// so it is dispatched here instead of through `callback_impl!`.
+impl OnKey for Dialog {
+    fn set_on_key<Cb>(self, on_key: Cb) -> Self where Cb: Callback<(Self, KeyEvent)> {
+        with_dialog_keys(self, |keys| keys.on_key = Some(Rc::new(RefCell::new(Box::new(on_key)))));
+        self
+    }
+
+    fn set_on_key_press<Cb>(self, on_key_press: Cb) -> Self
+    where Cb: Callback<(Self, KeyEvent, bool)> {
+        callback_impl! {
+            ::attrs::KEYPRESS_CB, self, on_key_press, Dialog, (Dialog, KeyEvent, bool),
+            |element, c: c_int, press: c_int|
+                (Dialog::from_ptr(element), KeyEvent::from_code(c), press != 0)
+        }
+        self
+    }
+}
+
+type SharedCallback<Args> = Rc<RefCell<Box<dyn Callback<Args>>>>;
+
+#[derive(Default)]
+struct DialogKeys {
+    shortcuts: HashMap<KeyEvent, SharedCallback<Dialog>>,
+    /// The shortcuts registered for the items of the dialog's menu.
+    menu_shortcuts: Vec<KeyEvent>,
+    on_key: Option<SharedCallback<(Dialog, KeyEvent)>>,
+}
+
+thread_local!(
+    static DIALOG_KEYS: RefCell<HashMap<IUPPtr, DialogKeys>> = RefCell::new(HashMap::new())
+);
+
+fn forget_state(widget: Option<IUPPtr>) {
+    DIALOG_KEYS.with(|dialog_keys| ::forget_state(dialog_keys, widget));
+}
+
+fn install_key_dispatch(dialog: Dialog) {
+    dialog.set_callback(::attrs::K_ANY, unsafe {
+        ::std::mem::transmute::<*const (), iup_sys::Icallback>(dispatch_key as *const ())
+    });
+}
+
+fn with_dialog_keys<F, R>(dialog: Dialog, f: F) -> R where F: FnOnce(&mut DialogKeys) -> R {
+    ::KISSContext::track_state(dialog.ptr(), forget_state);
+
+    DIALOG_KEYS.with(|dialog_keys|
+        f(dialog_keys.borrow_mut().entry(dialog.ptr()).or_insert_with(DialogKeys::default))
+    )
+}
+
+extern "C" fn dispatch_key(element: *mut iup_sys::Ihandle, c: c_int) -> c_int {
+    let dialog = unsafe { Dialog::from_ptr(element) };
+    let key = KeyEvent::from_code(c);
+
+    // Clone the callbacks out so they can register shortcuts themselves without a double borrow.
+    let (shortcut, on_key) = DIALOG_KEYS.with(|dialog_keys|
+        dialog_keys.borrow().get(&element)
+            .map(|keys| (keys.shortcuts.get(&key).cloned(), keys.on_key.clone()))
+            .unwrap_or((None, None))
+    );
+
+    if let Some(shortcut) = shortcut {
+        return match shortcut.borrow_mut().on_callback(dialog) {
+            CallbackStatus::Default => CallbackStatus::Ignore,
+            status => status,
+        }.to_cb_return();
+    }
+
+    if let Some(target) = ::text::mnemonic_target(dialog, key) {
+        target.set_focus();
+        return CallbackStatus::Ignore.to_cb_return();
+    }
+
+    on_key.map(|on_key| on_key.borrow_mut().on_callback((dialog, key)))
+        .unwrap_or(CallbackStatus::Default)
+        .to_cb_return()
+}
+
+/// An error returned by `Dialog::add_shortcut()`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ShortcutError {
+    /// The shortcut string could not be parsed.
+    Parse(ParseKeyError),
+    /// A shortcut for the same keys is already registered on the dialog.
+    Conflict(KeyEvent),
+}
+
+impl Display for ShortcutError {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match *self {
+            ShortcutError::Parse(ref err) => write!(fmt, "invalid shortcut: {}", err),
+            ShortcutError::Conflict(ref shortcut) =>
+                write!(fmt, "the shortcut {} is already registered on this dialog", shortcut),
+        }
+    }
+}
+
+impl Error for ShortcutError {}
+
 impl_on_show! { Dialog }
 
+impl_on_drop_files! { Dialog }
+
+impl ::dragdrop::DragSource for Dialog {}
+
+impl ::dragdrop::DropTarget for Dialog {}
+
 /// Popup a message dialog and block until it is closed, by either the OK button or the exit
 /// button.
 pub fn message_popup<T: Into<String>, M: Into<String>>(title: T, message: M) {
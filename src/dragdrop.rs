@@ -0,0 +1,248 @@
+//! Dragging values between widgets.
+//!
+//! A widget set as a drag source produces a value when the user starts dragging it, which is
+//! serialized to bytes and delivered to any drop target accepting the same `DragPayload` type.
+//!
+//! For dropping files from the desktop, see `callback::OnDropFiles`.
+//!
+//! For more info, see the [IUP drag and drop][iup-dnd] documentation.
+//!
+//! [iup-dnd]: http://webserver2.tecgraf.puc-rio.br/iup/en/attrib/iup_dragdrop.html
+
+use widget_prelude::*;
+
+use callback::Callback;
+
+use iup_sys::{self, Ihandle};
+
+use libc::{c_char, c_int, c_void};
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ptr;
+use std::rc::Rc;
+use std::slice;
+
+/// A value that can be dragged between widgets.
+pub trait DragPayload: Sized + 'static {
+    /// The name identifying this type of payload. Only drop targets for the same name will
+    /// accept it.
+    ///
+    /// Use a name unique to the application for payloads that should not be dropped elsewhere.
+    fn drag_type() -> &'static str;
+
+    fn to_bytes(&self) -> Vec<u8>;
+
+    /// Deserialize a payload, returning `None` if `bytes` is not valid for this type.
+    fn from_bytes(bytes: &[u8]) -> Option<Self>;
+}
+
+impl DragPayload for String {
+    fn drag_type() -> &'static str {
+        "TEXT"
+    }
+
+    fn to_bytes(&self) -> Vec<u8> {
+        self.clone().into_bytes()
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Option<String> {
+        String::from_utf8(bytes.to_owned()).ok()
+    }
+}
+
+/// How a drag operation ended.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DragEnd {
+    /// The payload was copied to the target; the source should keep its value.
+    Copy,
+    /// The payload was moved to the target; the source should remove its value.
+    Move,
+    /// The drag was cancelled or not accepted by any target.
+    Cancel,
+}
+
+impl DragEnd {
+    fn from_action(action: c_int) -> DragEnd {
+        match action {
+            1 => DragEnd::Move,
+            0 => DragEnd::Copy,
+            _ => DragEnd::Cancel,
+        }
+    }
+}
+
+/// A trait describing a widget which values can be dragged from.
+pub trait DragSource: Widget {
+    /// Make this widget a source for dragging values of type `T`.
+    ///
+    /// `on_drag_begin` is invoked with the position where the drag started, relative to the
+    /// widget, and should return the value to drag or `None` to not start dragging.
+    ///
+    /// If `allow_move` is `true`, the user can choose to move the value instead of copying it
+    /// (usually by holding Shift); see `set_on_drag_end()`.
+    fn set_drag_source<T, F>(self, allow_move: bool, mut on_drag_begin: F) -> Self
+    where T: DragPayload, F: FnMut(Self, i32, i32) -> Option<T> + 'static {
+        let begin = move |ptr, x, y| {
+            let widget = unsafe { Self::from_ptr(ptr) };
+            on_drag_begin(widget, x, y).map(|payload| payload.to_bytes())
+        };
+
+        SOURCES.with(|sources| sources.borrow_mut().insert(self.ptr(), Source {
+            begin: Rc::new(RefCell::new(Box::new(begin))),
+            data: Vec::new(),
+            on_end: None,
+        }));
+        ::KISSContext::track_state(self.ptr(), forget_state);
+
+        self.set_bool_attribute(::attrs::DRAGSOURCE, true);
+        self.set_str_attribute(::attrs::DRAGTYPES, T::drag_type());
+        self.set_bool_attribute(::attrs::DRAGSOURCEMOVE, allow_move);
+
+        set_extern_callback(self, ::attrs::DRAGBEGIN_CB, drag_begin as *const ());
+        set_extern_callback(self, ::attrs::DRAGDATASIZE_CB, drag_data_size as *const ());
+        set_extern_callback(self, ::attrs::DRAGDATA_CB, drag_data as *const ());
+        set_extern_callback(self, ::attrs::DRAGEND_CB, drag_end as *const ());
+        self
+    }
+
+    /// Set a callback to be invoked when a drag from this widget ends, with how it ended.
+    ///
+    /// Has no effect unless `set_drag_source()` was called first.
+    fn set_on_drag_end<Cb>(self, on_drag_end: Cb) -> Self where Cb: Callback<(Self, DragEnd)> {
+        let mut on_drag_end = on_drag_end;
+        let end = move |ptr, end| {
+            let widget = unsafe { Self::from_ptr(ptr) };
+            on_drag_end.on_callback((widget, end));
+        };
+
+        SOURCES.with(|sources|
+            sources.borrow_mut().get_mut(&self.ptr())
+                .map(|source| source.on_end = Some(Rc::new(RefCell::new(Box::new(end)))))
+        );
+
+        self
+    }
+}
+
+/// A trait describing a widget which values can be dropped onto.
+pub trait DropTarget: Widget {
+    /// Make this widget a target for dropping values of type `T`.
+    ///
+    /// `on_drop` is invoked with the value and the position of the drop relative to the widget.
+    fn set_drop_target<T, Cb>(self, on_drop: Cb) -> Self
+    where T: DragPayload, Cb: Callback<(Self, T, i32, i32)> {
+        let mut on_drop = on_drop;
+        let drop = move |ptr, bytes: &[u8], x, y| {
+            let widget = unsafe { Self::from_ptr(ptr) };
+
+            T::from_bytes(bytes)
+                .map(|payload| on_drop.on_callback((widget, payload, x, y)).to_cb_return())
+                .unwrap_or(iup_sys::IUP_IGNORE)
+        };
+
+        TARGETS.with(|targets|
+            targets.borrow_mut().insert(self.ptr(), Rc::new(RefCell::new(Box::new(drop))))
+        );
+        ::KISSContext::track_state(self.ptr(), forget_state);
+
+        self.set_bool_attribute(::attrs::DROPTARGET, true);
+        self.set_str_attribute(::attrs::DROPTYPES, T::drag_type());
+
+        set_extern_callback(self, ::attrs::DROPDATA_CB, drop_data as *const ());
+        self
+    }
+}
+
+type BeginFn = Rc<RefCell<Box<dyn FnMut(IUPPtr, i32, i32) -> Option<Vec<u8>>>>>;
+type EndFn = Rc<RefCell<Box<dyn FnMut(IUPPtr, DragEnd)>>>;
+type DropFn = Rc<RefCell<Box<dyn FnMut(IUPPtr, &[u8], i32, i32) -> c_int>>>;
+
+struct Source {
+    begin: BeginFn,
+    /// The serialized payload of the drag in progress.
+    data: Vec<u8>,
+    on_end: Option<EndFn>,
+}
+
+thread_local! {
+    static SOURCES: RefCell<HashMap<IUPPtr, Source>> = RefCell::new(HashMap::new());
+    static TARGETS: RefCell<HashMap<IUPPtr, DropFn>> = RefCell::new(HashMap::new());
+}
+
+fn forget_state(widget: Option<IUPPtr>) {
+    SOURCES.with(|sources| ::forget_state(sources, widget));
+    TARGETS.with(|targets| ::forget_state(targets, widget));
+}
+
+fn set_extern_callback<W: Widget>(widget: W, name: &'static str, callback: *const ()) {
+    widget.set_callback(name, unsafe {
+        ::std::mem::transmute::<*const (), iup_sys::Icallback>(callback)
+    });
+}
+
+extern "C" fn drag_begin(element: *mut Ihandle, x: c_int, y: c_int) -> c_int {
+    let begin = SOURCES.with(|sources|
+        sources.borrow().get(&element).map(|source| source.begin.clone())
+    );
+
+    let data = begin.and_then(|begin| (*begin.borrow_mut())(element, x, y));
+
+    SOURCES.with(|sources| {
+        match (sources.borrow_mut().get_mut(&element), data) {
+            (Some(source), Some(data)) => {
+                source.data = data;
+                iup_sys::IUP_DEFAULT
+            },
+            _ => iup_sys::IUP_IGNORE,
+        }
+    })
+}
+
+extern "C" fn drag_data_size(element: *mut Ihandle, _type: *const c_char) -> c_int {
+    SOURCES.with(|sources|
+        sources.borrow().get(&element).map_or(0, |source| source.data.len() as c_int)
+    )
+}
+
+extern "C" fn drag_data(element: *mut Ihandle, _type: *const c_char, data: *mut c_void,
+                        size: c_int) -> c_int {
+    SOURCES.with(|sources| {
+        if let Some(source) = sources.borrow().get(&element) {
+            let len = ::std::cmp::min(source.data.len(), size as usize);
+            unsafe { ptr::copy_nonoverlapping(source.data.as_ptr(), data as *mut u8, len); }
+        }
+    });
+
+    iup_sys::IUP_DEFAULT
+}
+
+extern "C" fn drag_end(element: *mut Ihandle, action: c_int) -> c_int {
+    let on_end = SOURCES.with(|sources| {
+        sources.borrow_mut().get_mut(&element).and_then(|source| {
+            source.data = Vec::new();
+            source.on_end.clone()
+        })
+    });
+
+    if let Some(on_end) = on_end {
+        (*on_end.borrow_mut())(element, DragEnd::from_action(action));
+    }
+
+    iup_sys::IUP_DEFAULT
+}
+
+extern "C" fn drop_data(element: *mut Ihandle, _type: *const c_char, data: *const c_void,
+                        size: c_int, x: c_int, y: c_int) -> c_int {
+    let drop = TARGETS.with(|targets| targets.borrow().get(&element).cloned());
+
+    drop.map_or(iup_sys::IUP_IGNORE, |drop| {
+        let bytes = if data.is_null() || size <= 0 {
+            &[][..]
+        } else {
+            unsafe { slice::from_raw_parts(data as *const u8, size as usize) }
+        };
+
+        (*drop.borrow_mut())(element, bytes, x, y)
+    })
+}
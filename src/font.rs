@@ -0,0 +1,270 @@
+//! Fonts and text measurement.
+//!
+//! Fonts are described to IUP as strings in the form `"Face, Style Size"`, e.g.
+//! `"Times, Bold Italic 12"`. The `Font` type in this module can be parsed from and formatted
+//! to this syntax.
+//!
+//! For more info, see the [IUP `FONT` attribute][iup-font] documentation.
+//!
+//! [iup-font]: http://webserver2.tecgraf.puc-rio.br/iup/en/attrib/iup_font.html
+
+use widget_prelude::*;
+
+use base::BaseWidget;
+
+use std::error::Error;
+use std::ffi::CString;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// A font description: typeface, size, and style flags.
+///
+/// Positive sizes are in points, negative sizes are in pixels, following IUP's convention.
+/// A size of `0` lets the platform choose.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Font {
+    pub face: String,
+    pub size: i32,
+    pub bold: bool,
+    pub italic: bool,
+    pub underline: bool,
+    pub strikeout: bool,
+}
+
+impl Font {
+    /// Create a new font with the given typeface and size in points, and no style flags set.
+    pub fn new<F: Into<String>>(face: F, size: i32) -> Font {
+        Font {
+            face: face.into(),
+            size,
+            bold: false,
+            italic: false,
+            underline: false,
+            strikeout: false,
+        }
+    }
+
+    /// Set the size of this font in points.
+    pub fn size(mut self, size: i32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the size of this font in pixels.
+    pub fn size_pixels(mut self, size: u32) -> Self {
+        self.size = -(size as i32);
+        self
+    }
+
+    pub fn bold(mut self, bold: bool) -> Self {
+        self.bold = bold;
+        self
+    }
+
+    pub fn italic(mut self, italic: bool) -> Self {
+        self.italic = italic;
+        self
+    }
+
+    pub fn underline(mut self, underline: bool) -> Self {
+        self.underline = underline;
+        self
+    }
+
+    pub fn strikeout(mut self, strikeout: bool) -> Self {
+        self.strikeout = strikeout;
+        self
+    }
+}
+
+impl Display for Font {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(fmt, "{},", self.face)?;
+
+        let styles = [
+            (self.bold, "Bold"),
+            (self.italic, "Italic"),
+            (self.underline, "Underline"),
+            (self.strikeout, "Strikeout"),
+        ];
+
+        for &(_, style) in styles.iter().filter(|&&(set, _)| set) {
+            write!(fmt, " {}", style)?;
+        }
+
+        if self.size != 0 {
+            write!(fmt, " {}", self.size)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Font {
+    type Err = ParseFontError;
+
+    /// Parse a font from IUP's `"Face, Style Size"` syntax.
+    ///
+    /// Style names are matched case-insensitively; the style and size may both be omitted.
+    fn from_str(s: &str) -> Result<Font, ParseFontError> {
+        let comma = s.find(',').ok_or(ParseFontError::MissingComma)?;
+        let face = s[..comma].trim();
+
+        if face.is_empty() {
+            return Err(ParseFontError::MissingFace);
+        }
+
+        let mut font = Font::new(face, 0);
+
+        for token in s[comma + 1..].split_whitespace() {
+            match &*token.to_lowercase() {
+                "bold" => font.bold = true,
+                "italic" => font.italic = true,
+                "underline" => font.underline = true,
+                "strikeout" => font.strikeout = true,
+                // Accepted by IUP, but equivalent to no style at all.
+                "normal" => (),
+                _ => font.size = token.parse()
+                    .map_err(|_| ParseFontError::InvalidToken(token.to_owned()))?,
+            }
+        }
+
+        Ok(font)
+    }
+}
+
+/// An error returned when parsing a `Font` from a string fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseFontError {
+    /// The string had no comma separating the typeface from the style and size.
+    MissingComma,
+    /// The typeface before the comma was empty.
+    MissingFace,
+    /// A token after the comma was neither a known style nor an integer size.
+    InvalidToken(String),
+}
+
+impl Display for ParseFontError {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        use self::ParseFontError::*;
+
+        match *self {
+            MissingComma => fmt.write_str("expected a comma after the font face"),
+            MissingFace => fmt.write_str("the font face was empty"),
+            InvalidToken(ref token) => write!(fmt, "expected a font style or size, got {:?}", token),
+        }
+    }
+}
+
+impl Error for ParseFontError {}
+
+/// Set the font used by all widgets that do not have a font set explicitly.
+///
+/// Only affects widgets created after this call.
+///
+/// ##Panics
+/// If called outside a valid KISS-UI context.
+pub fn set_default_font(font: &Font) {
+    ::set_global(::attrs::DEFAULT_FONT, font.to_string());
+}
+
+/// Get the font used by all widgets that do not have a font set explicitly.
+///
+/// ##Panics
+/// If called outside a valid KISS-UI context.
+pub fn get_default_font() -> Option<Font> {
+    ::get_global(::attrs::DEFAULT_FONT).and_then(|font| font.parse().ok())
+}
+
+/// Measurements of text rendered in a widget's current font.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct TextMetrics {
+    /// The average width of a character, in pixels.
+    pub char_width: u32,
+    /// The height of a line of text, in pixels.
+    pub char_height: u32,
+}
+
+impl TextMetrics {
+    /// Get the metrics of the font currently used by `widget`.
+    pub fn of<W: Widget>(widget: W) -> TextMetrics {
+        let (width, height) = widget.get_int2_attribute(::attrs::CHARSIZE);
+
+        TextMetrics {
+            char_width: width as u32,
+            char_height: height as u32,
+        }
+    }
+
+    /// Estimate the size of `text` in pixels when rendered in this font, as `(width, height)`.
+    ///
+    /// Each line is estimated as its character count times the average character width, so
+    /// this is inexact for proportional fonts. Use `text_size()` for the exact size.
+    pub fn approx_text_size(&self, text: &str) -> (u32, u32) {
+        let (cols, lines) = text.lines().fold((0, 0), |(cols, lines), line|
+            (::std::cmp::max(cols, line.chars().count() as u32), lines + 1)
+        );
+
+        (cols * self.char_width, ::std::cmp::max(lines, 1) * self.char_height)
+    }
+}
+
+/// Measure the size of `text` in pixels when rendered in the current font of `widget`, as
+/// `(width, height)`. Lines are separated by `\n`.
+///
+/// ##Panics
+/// If called outside a valid KISS-UI context.
+pub fn text_size<W: Widget>(widget: W, text: &str) -> (u32, u32) {
+    assert_kiss_running!();
+
+    // A hidden, unmapped label has exactly the natural size of its text, as laid out by IUP.
+    let c_text = CString::new(text.replace('&', "&&")).unwrap();
+
+    unsafe {
+        let label = BaseWidget::from_ptr(::iup_sys::IupLabel(c_text.as_ptr()));
+        let dialog = ::iup_sys::IupDialog(label.ptr());
+
+        if let Some(font) = widget.get_str_attribute(::attrs::FONT) {
+            label.set_str_attribute(::attrs::FONT, &*font);
+        }
+
+        label.set_const_str_attribute(::attrs::PADDING, cstr!("0x0"));
+        ::iup_sys::IupRefresh(dialog);
+
+        let size = label.get_int2_attribute(::attrs::NATURALSIZE);
+        ::iup_sys::IupDestroy(dialog);
+
+        (size.0 as u32, size.1 as u32)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Font, ParseFontError};
+
+    #[test]
+    fn parse() {
+        let times = Font::new("Times", 12).bold(true).italic(true);
+        assert_eq!("Times, Bold Italic 12".parse(), Ok(times));
+
+        let courier = Font::new("Courier New", 0).underline(true).strikeout(true);
+        assert_eq!(" Courier New ,underline STRIKEOUT".parse(), Ok(courier));
+
+        assert_eq!("Arial, Normal -16".parse(), Ok(Font::new("Arial", 0).size_pixels(16)));
+        assert_eq!("Arial,".parse(), Ok(Font::new("Arial", 0)));
+
+        assert_eq!("Arial 12".parse::<Font>(), Err(ParseFontError::MissingComma));
+        assert_eq!(" , 12".parse::<Font>(), Err(ParseFontError::MissingFace));
+        let heavy = ParseFontError::InvalidToken("Heavy".into());
+        assert_eq!("Arial, Heavy".parse::<Font>(), Err(heavy));
+    }
+
+    #[test]
+    fn format_round_trips() {
+        let font = Font::new("Times", 12).bold(true).strikeout(true);
+        assert_eq!(font.to_string(), "Times, Bold Strikeout 12");
+        assert_eq!(font.to_string().parse(), Ok(font));
+
+        assert_eq!(Font::new("Times", 0).to_string(), "Times,");
+    }
+}
@@ -82,6 +82,29 @@ pub fn transmute_buffer_rgba(buf: &[u8]) -> Option<&[(u8, u8, u8, u8)]> {
     }
 }
 
+/// Where an image is placed relative to the text next to it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ImagePosition {
+    Left,
+    Right,
+    Top,
+    Bottom,
+}
+
+impl ImagePosition {
+    #[doc(hidden)]
+    pub fn as_cstr(self) -> &'static str {
+        use self::ImagePosition::*;
+
+        match self {
+            Left => cstr!("LEFT"),
+            Right => cstr!("RIGHT"),
+            Top => cstr!("TOP"),
+            Bottom => cstr!("BOTTOM"),
+        }
+    }
+}
+
 /// A trait describing an object that can render an image within itself.
 pub trait ImageContainer: Widget {
     /// Set the image this widget is to render and return `self` for method chaining.
@@ -0,0 +1,426 @@
+//! Keyboard input: typed keys, modifiers, and shortcut syntax.
+//!
+//! IUP reports keys as integer codes where the low bits identify the key and the high bits flag
+//! the modifiers held at the time. `KeyEvent` decodes these codes, and can be parsed from and
+//! formatted to shortcut strings like `"Ctrl+Shift+S"`.
+//!
+//! For more info, see the [IUP keyboard codes][iup-keys] documentation.
+//!
+//! [iup-keys]: http://webserver2.tecgraf.puc-rio.br/iup/en/attrib/key.html
+
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+const SHIFT_FLAG: u32 = 0x1000_0000;
+const CTRL_FLAG: u32 = 0x2000_0000;
+const ALT_FLAG: u32 = 0x4000_0000;
+const SYS_FLAG: u32 = 0x8000_0000;
+const KEY_MASK: u32 = 0x0FFF_FFFF;
+
+const F1_CODE: u32 = 0xFFBE;
+const F_KEYS: u8 = 12;
+/// Codes from here up are special keys, in the same ranges as X11 keysyms.
+const SPECIAL_CODES: u32 = 0xFF00;
+
+macro_rules! named_keys {
+    ($($key:ident = $code:expr, $name:expr;)+) => (
+        /// A key on the keyboard, independent of the modifiers held with it.
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        pub enum Key {
+            /// A key which produces a printable character: letters, digits and punctuation.
+            ///
+            /// Letters are reported as typed, except when Ctrl, Alt or Sys are held, where they
+            /// are always uppercase.
+            Char(char),
+            /// A function key, numbered from `1` to `12`.
+            F(u8),
+            $($key,)+
+            /// A key with no other representation, by its IUP code without modifiers.
+            Other(u32),
+        }
+
+        impl Key {
+            fn from_named_code(code: u32) -> Option<Key> {
+                match code {
+                    $($code => Some(Key::$key),)+
+                    _ => None,
+                }
+            }
+
+            fn named_code(self) -> Option<u32> {
+                match self {
+                    $(Key::$key => Some($code),)+
+                    _ => None,
+                }
+            }
+
+            fn name(self) -> Option<&'static str> {
+                match self {
+                    $(Key::$key => Some($name),)+
+                    _ => None,
+                }
+            }
+
+            fn from_name(name: &str) -> Option<Key> {
+                $(
+                    if name.eq_ignore_ascii_case($name) { return Some(Key::$key); }
+                )+
+
+                None
+            }
+        }
+    )
+}
+
+named_keys! {
+    Backspace = 0x08, "Backspace";
+    Tab = 0x09, "Tab";
+    Enter = 0x0D, "Enter";
+    Space = 0x20, "Space";
+    Pause = 0xFF13, "Pause";
+    ScrollLock = 0xFF14, "ScrollLock";
+    Escape = 0xFF1B, "Esc";
+    Home = 0xFF50, "Home";
+    Left = 0xFF51, "Left";
+    Up = 0xFF52, "Up";
+    Right = 0xFF53, "Right";
+    Down = 0xFF54, "Down";
+    PageUp = 0xFF55, "PageUp";
+    PageDown = 0xFF56, "PageDown";
+    End = 0xFF57, "End";
+    Print = 0xFF61, "Print";
+    Insert = 0xFF63, "Insert";
+    Menu = 0xFF67, "Menu";
+    NumLock = 0xFF7F, "NumLock";
+    LeftShift = 0xFFE1, "LeftShift";
+    RightShift = 0xFFE2, "RightShift";
+    LeftCtrl = 0xFFE3, "LeftCtrl";
+    RightCtrl = 0xFFE4, "RightCtrl";
+    CapsLock = 0xFFE5, "CapsLock";
+    LeftAlt = 0xFFE9, "LeftAlt";
+    RightAlt = 0xFFEA, "RightAlt";
+    LeftSys = 0xFFEB, "LeftSys";
+    RightSys = 0xFFEC, "RightSys";
+    Delete = 0xFFFF, "Delete";
+}
+
+impl Key {
+    /// Decode a key from an IUP key code, ignoring any modifier flags.
+    pub fn from_code(code: u32) -> Key {
+        let code = code & KEY_MASK;
+
+        if let Some(key) = Key::from_named_code(code) {
+            return key;
+        }
+
+        if code >= F1_CODE && code < F1_CODE + F_KEYS as u32 {
+            return Key::F((code - F1_CODE) as u8 + 1);
+        }
+
+        if code >= SPECIAL_CODES {
+            return Key::Other(code);
+        }
+
+        ::std::char::from_u32(code)
+            .filter(|c| !c.is_control())
+            .map_or(Key::Other(code), Key::Char)
+    }
+
+    /// Get the IUP key code for this key, without modifier flags.
+    pub fn to_code(self) -> u32 {
+        match self {
+            Key::Char(c) => c as u32,
+            Key::F(num) => F1_CODE + num as u32 - 1,
+            Key::Other(code) => code,
+            named => named.named_code().expect("All remaining keys are named"),
+        }
+    }
+}
+
+impl Display for Key {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        match *self {
+            Key::Char(c) => write!(fmt, "{}", c),
+            Key::F(num) => write!(fmt, "F{}", num),
+            Key::Other(code) => write!(fmt, "{:#X}", code),
+            named => fmt.write_str(named.name().expect("All remaining keys are named")),
+        }
+    }
+}
+
+impl FromStr for Key {
+    type Err = ParseKeyError;
+
+    /// Parse a key from its name as formatted by `Display`, or from a single character.
+    ///
+    /// Names are matched case-insensitively. `"Return"` and `"Escape"` are also accepted.
+    fn from_str(s: &str) -> Result<Key, ParseKeyError> {
+        let mut chars = s.chars();
+
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            return Ok(Key::Char(c));
+        }
+
+        if let Some(key) = Key::from_name(s) {
+            return Ok(key);
+        }
+
+        if s.eq_ignore_ascii_case("Return") {
+            return Ok(Key::Enter);
+        }
+
+        if s.eq_ignore_ascii_case("Escape") {
+            return Ok(Key::Escape);
+        }
+
+        if s.len() > 1 && (s.starts_with('F') || s.starts_with('f')) {
+            if let Ok(num) = s[1..].parse() {
+                if (1..=F_KEYS).contains(&num) {
+                    return Ok(Key::F(num));
+                }
+            }
+        }
+
+        Err(ParseKeyError::UnknownKey(s.to_owned()))
+    }
+}
+
+/// The modifier keys held during a keyboard or mouse event.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    /// The Windows key, or the Command key on OS X.
+    pub sys: bool,
+}
+
+impl Modifiers {
+    /// Decode the modifier flags from an IUP key code.
+    pub fn from_code(code: u32) -> Modifiers {
+        Modifiers {
+            ctrl: code & CTRL_FLAG != 0,
+            shift: code & SHIFT_FLAG != 0,
+            alt: code & ALT_FLAG != 0,
+            sys: code & SYS_FLAG != 0,
+        }
+    }
+
+    /// Get the IUP modifier flags for these modifiers, to be combined with a key code.
+    pub fn to_code(self) -> u32 {
+        let flag = |set, flag| if set { flag } else { 0 };
+
+        flag(self.ctrl, CTRL_FLAG) | flag(self.shift, SHIFT_FLAG)
+            | flag(self.alt, ALT_FLAG) | flag(self.sys, SYS_FLAG)
+    }
+
+    /// Returns `true` if no modifiers are held.
+    pub fn is_empty(&self) -> bool {
+        *self == Modifiers::default()
+    }
+}
+
+impl Display for Modifiers {
+    /// Format the modifiers held as they appear in a shortcut, e.g. `"Ctrl+Shift+"`.
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        let names = [
+            (self.ctrl, "Ctrl+"),
+            (self.shift, "Shift+"),
+            (self.alt, "Alt+"),
+            (self.sys, "Sys+"),
+        ];
+
+        for &(_, name) in names.iter().filter(|&&(set, _)| set) {
+            fmt.write_str(name)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A key combined with the modifiers held when it was pressed.
+///
+/// Can be parsed from and formatted to shortcut strings like `"Ctrl+Shift+S"` or `"F5"`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct KeyEvent {
+    pub key: Key,
+    pub modifiers: Modifiers,
+}
+
+impl KeyEvent {
+    /// Create a new key event, normalizing letters to uppercase if Ctrl, Alt or Sys are held,
+    /// as IUP does.
+    pub fn new(key: Key, modifiers: Modifiers) -> KeyEvent {
+        let key = match key {
+            Key::Char(c) if modifiers.ctrl || modifiers.alt || modifiers.sys =>
+                Key::Char(c.to_ascii_uppercase()),
+            key => key,
+        };
+
+        KeyEvent { key, modifiers }
+    }
+
+    /// Decode a key event from an IUP key code, as passed to `K_ANY` and `KEYPRESS_CB`.
+    pub fn from_code(code: i32) -> KeyEvent {
+        let code = code as u32;
+        KeyEvent::new(Key::from_code(code), Modifiers::from_code(code))
+    }
+
+    /// Get the IUP key code for this key event, including modifier flags.
+    pub fn to_code(self) -> i32 {
+        (self.key.to_code() | self.modifiers.to_code()) as i32
+    }
+}
+
+impl Display for KeyEvent {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(fmt, "{}{}", self.modifiers, self.key)
+    }
+}
+
+impl FromStr for KeyEvent {
+    type Err = ParseKeyError;
+
+    /// Parse a shortcut string like `"Ctrl+Shift+S"`, `"Alt+F4"` or `"Ctrl++"`.
+    ///
+    /// Modifier names are matched case-insensitively. `"Control"`, `"Meta"` and `"Cmd"` are also
+    /// accepted.
+    fn from_str(s: &str) -> Result<KeyEvent, ParseKeyError> {
+        let s = s.trim();
+
+        // The key itself may be `+`, which would otherwise be taken as a separator.
+        let split = if s.ends_with("++") || s == "+" {
+            s.len() - 1
+        } else {
+            s.rfind('+').map_or(0, |idx| idx + 1)
+        };
+
+        let (mods, key) = s.split_at(split);
+
+        if key.is_empty() {
+            return Err(ParseKeyError::MissingKey);
+        }
+
+        let mut modifiers = Modifiers::default();
+
+        for modifier in mods.split('+').filter(|m| !m.is_empty()) {
+            match &*modifier.trim().to_lowercase() {
+                "ctrl" | "control" => modifiers.ctrl = true,
+                "shift" => modifiers.shift = true,
+                "alt" => modifiers.alt = true,
+                "sys" | "meta" | "cmd" => modifiers.sys = true,
+                _ => return Err(ParseKeyError::UnknownModifier(modifier.to_owned())),
+            }
+        }
+
+        Ok(KeyEvent::new(key.trim().parse()?, modifiers))
+    }
+}
+
+impl From<Key> for KeyEvent {
+    fn from(key: Key) -> KeyEvent {
+        KeyEvent::new(key, Modifiers::default())
+    }
+}
+
+/// Get the mnemonic character of a widget title, i.e. the character following the first single
+/// `&`, if any.
+///
+/// `&&` is an escaped `&` and does not define a mnemonic.
+pub fn mnemonic(title: &str) -> Option<char> {
+    let mut chars = title.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '&' {
+            match chars.next() {
+                Some('&') => continue,
+                next => return next,
+            }
+        }
+    }
+
+    None
+}
+
+/// An error returned when parsing a `Key` or `KeyEvent` from a string fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseKeyError {
+    /// The shortcut ended with a modifier and had no key.
+    MissingKey,
+    /// A modifier name was not recognized.
+    UnknownModifier(String),
+    /// A key name was not recognized.
+    UnknownKey(String),
+}
+
+impl Display for ParseKeyError {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        use self::ParseKeyError::*;
+
+        match *self {
+            MissingKey => fmt.write_str("expected a key after the modifiers"),
+            UnknownModifier(ref modifier) => write!(fmt, "unknown modifier {:?}", modifier),
+            UnknownKey(ref key) => write!(fmt, "unknown key {:?}", key),
+        }
+    }
+}
+
+impl Error for ParseKeyError {}
+
+#[cfg(test)]
+mod tests {
+    use super::{mnemonic, Key, KeyEvent, Modifiers, ParseKeyError};
+
+    fn ctrl() -> Modifiers {
+        Modifiers { ctrl: true, ..Modifiers::default() }
+    }
+
+    #[test]
+    fn parse_shortcuts() {
+        assert_eq!("Ctrl+s".parse(), Ok(KeyEvent::new(Key::Char('S'), ctrl())));
+        assert_eq!("control + Shift + F5".parse(), Ok(KeyEvent::new(Key::F(5), Modifiers {
+            shift: true, ..ctrl()
+        })));
+        assert_eq!("Ctrl++".parse(), Ok(KeyEvent::new(Key::Char('+'), ctrl())));
+        assert_eq!("+".parse(), Ok(KeyEvent::from(Key::Char('+'))));
+        assert_eq!("escape".parse(), Ok(KeyEvent::from(Key::Escape)));
+        assert_eq!("Cmd+Return".parse(), Ok(KeyEvent::new(Key::Enter, Modifiers {
+            sys: true, ..Modifiers::default()
+        })));
+
+        assert_eq!("Ctrl+".parse::<KeyEvent>(), Err(ParseKeyError::MissingKey));
+        let hyper = ParseKeyError::UnknownModifier("Hyper".into());
+        assert_eq!("Hyper+A".parse::<KeyEvent>(), Err(hyper));
+        assert_eq!("F13".parse::<KeyEvent>(), Err(ParseKeyError::UnknownKey("F13".into())));
+    }
+
+    #[test]
+    fn format_shortcuts() {
+        let event = KeyEvent::new(Key::Char('s'), Modifiers { shift: true, ..ctrl() });
+        assert_eq!(event.to_string(), "Ctrl+Shift+S");
+        assert_eq!(event.to_string().parse(), Ok(event));
+
+        assert_eq!(KeyEvent::from(Key::PageDown).to_string(), "PageDown");
+        assert_eq!(KeyEvent::from(Key::Other(0xFF20)).to_string(), "0xFF20");
+    }
+
+    #[test]
+    fn codes() {
+        let event = KeyEvent::new(Key::F(4), Modifiers { alt: true, ..Modifiers::default() });
+        assert_eq!(KeyEvent::from_code(event.to_code()), event);
+
+        assert_eq!(Key::from_code(0xFF1B), Key::Escape);
+        assert_eq!(Key::from_code('a' as u32 | 0x2000_0000), Key::Char('a'));
+        assert_eq!(Key::from_code(0xFFC9), Key::F(12));
+        assert_eq!(Key::from_code(0xFF20), Key::Other(0xFF20));
+        assert_eq!(Key::from_code(0x1B), Key::Other(0x1B));
+    }
+
+    #[test]
+    fn mnemonics() {
+        assert_eq!(mnemonic("&File"), Some('F'));
+        assert_eq!(mnemonic("Save && E&xit"), Some('x'));
+        assert_eq!(mnemonic("Fish && Chips"), None);
+    }
+}
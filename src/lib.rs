@@ -53,23 +53,38 @@ pub mod callback;
 
 pub mod base;
 pub mod button;
+pub mod canvas;
+pub mod clipboard;
+pub mod color;
 pub mod container;
 pub mod dialog;
+pub mod dragdrop;
+pub mod font;
 pub mod image;
+pub mod keyboard;
+pub mod link;
+pub mod list;
+pub mod menu;
+pub mod mouse;
 pub mod progress;
+pub mod slider;
+pub mod spinbox;
 pub mod text;
 pub mod timer;
+pub mod tooltip;
+pub mod validate;
 
 use std::borrow::Borrow;
 use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
+use std::ffi::{CStr, CString};
 use std::ptr;
 use std::rc::Rc;
 use std::sync::atomic::{AtomicBool, ATOMIC_BOOL_INIT, Ordering};
 
 use base::BaseWidget;
 use dialog::Dialog;
-use widget::Widget;
+use widget::{IUPWidget, Widget};
 
 use utils::cstr::AsCStr;
 
@@ -84,8 +99,10 @@ mod widget_prelude {
 pub mod prelude {
     pub use base::BaseWidget;
     pub use dialog::Dialog;
-    pub use container::Orientation;
-    pub use callback::{CallbackStatus, OnClick, OnShow, OnValueChange};
+    pub use color::Color;
+    pub use container::{Expand, Orientation, Padding};
+    pub use font::Font;
+    pub use callback::{CallbackStatus, OnClick, OnFocus, OnKey, OnShow, OnTooltip, OnValueChange};
 
     pub use widget::{Widget, Destroy};
 }
@@ -98,7 +115,9 @@ thread_local! { static CONTEXT: KISSContext = KISSContext::default() }
 struct KISSContext {
     widget_store: RefCell<HashMap<String, BaseWidget>>,
     // FIXME: use Rc<()> once Rc::is_unique stabilizes
-    borrowed_strs: RefCell<HashMap<IUPPtr, HashMap<&'static str, Rc<Cell<usize>>>>>,
+    borrowed_strs: RefCell<HashMap<IUPPtr, StrRefcounts>>,
+    state_owners: RefCell<Vec<ForgetFn>>,
+    destroy_callbacks: RefCell<HashMap<IUPPtr, iup_sys::Icallback>>,
 }
 
 impl KISSContext {
@@ -145,12 +164,85 @@ impl KISSContext {
         )
     }
 
-    unsafe fn clear() {
+    /// Call `forget` with `widget` when it is destroyed, and with `None` when IUP is closed.
+    ///
+    /// Should be called whenever a module adds an entry for `widget` to its state.
+    fn track_state(widget: IUPPtr, forget: ForgetFn) {
         CONTEXT.with(|context| {
+            let mut state_owners = context.state_owners.borrow_mut();
+
+            if !state_owners.iter().any(|&owner| owner as usize == forget as usize) {
+                state_owners.push(forget);
+            }
+        });
+
+        let destroyed = unsafe {
+            ::std::mem::transmute::<*const (), iup_sys::Icallback>(widget_destroyed as *const ())
+        };
+
+        let widget = unsafe { BaseWidget::from_ptr(widget) };
+
+        // Keep any destroy callback set by the user so it is still invoked.
+        match widget.get_callback(::attrs::LDESTROY_CB) {
+            Some(callback) if callback as usize == destroyed as usize => (),
+            previous => {
+                if let Some(previous) = previous {
+                    CONTEXT.with(|context|
+                        context.destroy_callbacks.borrow_mut().insert(widget.ptr(), previous)
+                    );
+                }
+
+                widget.set_callback(::attrs::LDESTROY_CB, destroyed);
+            },
+        }
+    }
+
+    unsafe fn clear() {
+        let state_owners = CONTEXT.with(|context| {
             context.widget_store.borrow_mut().clear();
             context.borrowed_strs.borrow_mut().clear();
-        })
+            context.destroy_callbacks.borrow_mut().clear();
+            context.state_owners.borrow().clone()
+        });
+
+        for forget in state_owners {
+            forget(None);
+        }
+    }
+}
+
+/// Removes the state a module keeps for a widget, or for all widgets if `None`.
+///
+/// Entries must not outlive their widgets, or a new widget allocated at the same address would
+/// inherit them.
+type ForgetFn = fn(Option<IUPPtr>);
+
+/// The refcounts of the `WidgetStr`s borrowed from each string attribute of a widget.
+type StrRefcounts = HashMap<&'static str, Rc<Cell<usize>>>;
+
+extern "C" fn widget_destroyed(widget: IUPPtr) -> libc::c_int {
+    let (state_owners, previous) = CONTEXT.with(|context| {
+        context.borrowed_strs.borrow_mut().remove(&widget);
+
+        (context.state_owners.borrow().clone(),
+            context.destroy_callbacks.borrow_mut().remove(&widget))
+    });
+
+    for forget in state_owners {
+        forget(Some(widget));
     }
+
+    previous.map_or(iup_sys::IUP_DEFAULT, |previous| previous(widget))
+}
+
+/// Remove the entry for `widget` from `state`, or all entries if `None`.
+///
+/// The entries are dropped after `state` is released, in case they hold callbacks which use it.
+fn forget_state<V>(state: &RefCell<HashMap<IUPPtr, V>>, widget: Option<IUPPtr>) {
+    let _forgotten = match widget {
+        Some(widget) => state.borrow_mut().remove(&widget).into_iter().collect(),
+        None => state.borrow_mut().drain().map(|(_, val)| val).collect::<Vec<_>>(),
+    };
 }
 
 
@@ -201,3 +293,39 @@ pub fn show_gui<F>(init_fn: F) where F: FnOnce() -> Dialog + Send {
 
     KISS_RUNNING.store(false, Ordering::SeqCst); 
 }
+
+/// Open `url` in the system's default browser, or a file path in its default application.
+///
+/// Returns `false` if it could not be opened.
+///
+/// For more info, see the [`IupHelp`][iup-help] documentation.
+///
+/// [iup-help]: http://webserver2.tecgraf.puc-rio.br/iup/en/func/iuphelp.html
+pub fn open_url(url: &str) -> bool {
+    assert_kiss_running!();
+
+    let c_url = CString::new(url).unwrap();
+    unsafe { iup_sys::IupHelp(c_url.as_ptr()) == 1 }
+}
+
+fn set_global<V: Into<String>>(name: &'static str, val: V) {
+    assert_kiss_running!();
+
+    let c_val = CString::new(val.into()).unwrap();
+    unsafe { iup_sys::IupSetStrGlobal(name.as_cstr(), c_val.as_ptr()); }
+}
+
+fn get_global(name: &'static str) -> Option<String> {
+    assert_kiss_running!();
+
+    unsafe {
+        let ptr = iup_sys::IupGetGlobal(name.as_cstr());
+
+        if !ptr.is_null() {
+            // We're forcing IUP to use UTF-8
+            Some(CStr::from_ptr(ptr).to_string_lossy().into_owned())
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,92 @@
+//! Clickable hyperlinks.
+
+use widget_prelude::*;
+
+use std::ffi::CString;
+
+/// A label rendered as a hyperlink, which opens its URL in the system's browser when clicked.
+///
+/// A callback set with `OnClick::set_onclick()` is invoked first, and can return
+/// `CallbackStatus::Ignore` to handle the click itself instead of opening the URL. Use
+/// `kiss_ui::open_url()` to open it from the callback.
+///
+/// For more info, see the [`IupLink`][iup-link] documentation.
+///
+/// [iup-link]: http://webserver2.tecgraf.puc-rio.br/iup/en/elem/iuplink.html
+pub struct Link(IUPPtr);
+
+impl Link {
+    /// Create a new link showing `title` which opens `url`.
+    pub fn new<T: Into<String>, U: Into<String>>(title: T, url: U) -> Link {
+        let c_title = CString::new(title.into()).unwrap();
+        let c_url = CString::new(url.into()).unwrap();
+
+        unsafe {
+            let ptr = ::iup_sys::IupLink(c_url.as_ptr(), c_title.as_ptr());
+            Self::from_ptr(ptr)
+        }
+    }
+
+    /// Set the text of this link.
+    pub fn set_title<T: Into<String>>(self, title: T) -> Self {
+        self.set_str_attribute(::attrs::TITLE, title);
+        self
+    }
+
+    /// Get the text of this link.
+    pub fn get_title(&self) -> Option<WidgetStr<'_>> {
+        self.get_str_attribute(::attrs::TITLE)
+    }
+
+    /// Set the URL this link opens.
+    pub fn set_url<U: Into<String>>(self, url: U) -> Self {
+        self.set_str_attribute(::attrs::URL, url);
+        self
+    }
+
+    /// Get the URL this link opens.
+    pub fn get_url(&self) -> Option<WidgetStr<'_>> {
+        self.get_str_attribute(::attrs::URL)
+    }
+}
+
+impl_widget! { Link, "link" }
+
+impl_onclick! { Link }
+
+impl_on_tooltip! { Link }
+
+impl_on_mouse_enter_leave! { Link }
+
+#[cfg(test)]
+mod tests {
+    use super::Link;
+
+    use callback::{Callback, CallbackStatus};
+    use widget_prelude::*;
+
+    use std::cell::Cell;
+    use std::ptr::NonNull;
+    use std::rc::Rc;
+
+    // IupLink only opens its URL if the click callback returns `IUP_DEFAULT`.
+    #[test]
+    fn ignore_keeps_url_closed() {
+        let link = unsafe { Link::from_ptr(NonNull::dangling().as_ptr()) };
+        let clicked = Rc::new(Cell::new(false));
+
+        let mut on_click = {
+            let clicked = clicked.clone();
+            move |_: Link| {
+                clicked.set(true);
+                CallbackStatus::Ignore
+            }
+        };
+
+        assert_eq!(on_click.on_callback(link).to_cb_return(), ::iup_sys::IUP_IGNORE);
+        assert!(clicked.get());
+
+        let mut on_click = |_: Link| ();
+        assert_eq!(on_click.on_callback(link).to_cb_return(), ::iup_sys::IUP_DEFAULT);
+    }
+}
@@ -0,0 +1,759 @@
+//! Lists of items the user can select from, including dropdowns and combo boxes.
+
+use widget_prelude::*;
+
+use base::BaseWidget;
+use callback::{Callback, CallbackStatus};
+use color::Color;
+use font::TextMetrics;
+use image::Image;
+use keyboard::{Key, KeyEvent};
+use mouse::MouseStatus;
+
+use iup_sys::{self, Ihandle};
+
+use libc::{c_char, c_float, c_int};
+
+use std::any::Any;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::cmp;
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::ops::Range;
+use std::ptr;
+use std::rc::Rc;
+
+extern "C" {
+    // Not bound by `iup-sys`, but part of the IUP API since 3.13.
+    fn IupDrawBegin(ih: *mut Ihandle);
+    fn IupDrawEnd(ih: *mut Ihandle);
+    fn IupDrawGetSize(ih: *mut Ihandle, w: *mut c_int, h: *mut c_int);
+    fn IupDrawRectangle(ih: *mut Ihandle, x1: c_int, y1: c_int, x2: c_int, y2: c_int);
+    // Takes the size of the text box since 3.25; earlier versions are not supported.
+    fn IupDrawText(
+        ih: *mut Ihandle, text: *const c_char, len: c_int, x: c_int, y: c_int, w: c_int, h: c_int
+    );
+}
+
+/// How a `List` is presented and how many items can be selected.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ListMode {
+    /// A box showing all items, of which one can be selected.
+    Plain,
+    /// A button which shows the items in a popup when clicked, of which one can be selected.
+    Dropdown,
+    /// A text box with a dropdown of items; the user can select an item or type any text.
+    ComboBox,
+    /// A box showing all items, of which any number can be selected.
+    MultiSelect,
+}
+
+/// A list of items of type `T`, each displayed by its `Display` implementation.
+///
+/// For more info, see the [`IupList`][iup-list] documentation.
+///
+/// [iup-list]: http://webserver2.tecgraf.puc-rio.br/iup/en/elem/iuplist.html
+pub struct List<T>(IUPPtr, PhantomData<T>);
+
+struct ListData<T> {
+    mode: ListMode,
+    items: Vec<T>,
+}
+
+impl<T: Display + 'static> List<T> {
+    /// Create a new, empty list in the given mode.
+    ///
+    /// ##Note
+    /// The mode cannot be changed afterwards.
+    pub fn new(mode: ListMode) -> List<T> {
+        let list = unsafe {
+            let ptr = ::iup_sys::IupList(ptr::null());
+            Self::from_ptr(ptr)
+        };
+
+        match mode {
+            ListMode::Plain => (),
+            ListMode::Dropdown => list.set_bool_attribute(::attrs::DROPDOWN, true),
+            ListMode::ComboBox => {
+                list.set_bool_attribute(::attrs::DROPDOWN, true);
+                list.set_bool_attribute(::attrs::EDITBOX, true);
+            },
+            ListMode::MultiSelect => list.set_bool_attribute(::attrs::MULTIPLE, true),
+        }
+
+        LIST_DATA.with(|data|
+            data.borrow_mut().insert(list.ptr(), Box::new(ListData::<T> { mode, items: Vec::new() }))
+        );
+        ::KISSContext::track_state(list.ptr(), forget_list_data);
+
+        list
+    }
+
+    fn with_data<F, R>(&self, f: F) -> R where F: FnOnce(&mut ListData<T>) -> R {
+        LIST_DATA.with(|data| {
+            let mut data = data.borrow_mut();
+            let data = data.get_mut(&self.ptr())
+                .and_then(|data| data.downcast_mut::<ListData<T>>())
+                .expect("List items were not registered!");
+
+            f(data)
+        })
+    }
+
+    /// Get the mode this list was created with.
+    pub fn mode(&self) -> ListMode {
+        self.with_data(|data| data.mode)
+    }
+
+    /// Replace the items of this list, clearing the selection.
+    pub fn set_items(self, items: Vec<T>) -> Self {
+        self.set_const_str_attribute(::attrs::REMOVEITEM, cstr!("ALL"));
+
+        for item in &items {
+            self.set_str_attribute(::attrs::APPENDITEM, item.to_string());
+        }
+
+        self.with_data(|data| data.items = items);
+        self
+    }
+
+    /// Add an item to the end of this list.
+    pub fn push(self, item: T) -> Self {
+        self.set_str_attribute(::attrs::APPENDITEM, item.to_string());
+        self.with_data(|data| data.items.push(item));
+        self
+    }
+
+    /// Insert an item at `index`, shifting the items after it down.
+    ///
+    /// ##Panics
+    /// If `index` is greater than the number of items.
+    pub fn insert(self, index: usize, item: T) -> Self {
+        let len = self.len();
+        assert!(index <= len, "Insertion index {} out of bounds for List of length {}", index, len);
+
+        if index == len {
+            return self.push(item);
+        }
+
+        self.set_opt_str_attribute_id(
+            ::attrs::INSERTITEM, index as i32 + 1, Some(item.to_string())
+        );
+        self.with_data(|data| data.items.insert(index, item));
+        self
+    }
+
+    /// Remove and return the item at `index`, shifting the items after it up.
+    ///
+    /// ##Panics
+    /// If `index` is out of bounds.
+    pub fn remove(&self, index: usize) -> T {
+        let item = self.with_data(|data| data.items.remove(index));
+        self.set_int_attribute(::attrs::REMOVEITEM, index as i32 + 1);
+        item
+    }
+
+    /// Get the number of items in this list.
+    pub fn len(&self) -> usize {
+        self.with_data(|data| data.items.len())
+    }
+
+    /// Returns `true` if this list has no items.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Call `f` with the items of this list.
+    pub fn with_items<F, R>(&self, f: F) -> R where F: FnOnce(&[T]) -> R {
+        self.with_data(|data| f(&data.items))
+    }
+
+    /// Get the index of the selected item, if any.
+    ///
+    /// For `ListMode::ComboBox`, this is the first item matching the text in the edit box.
+    /// For `ListMode::MultiSelect`, this is the first selected item.
+    pub fn selected_index(&self) -> Option<usize> {
+        match self.mode() {
+            ListMode::MultiSelect => self.selected_indices().first().cloned(),
+            ListMode::ComboBox => {
+                let text = self.get_str_attribute(::attrs::VALUE)?.to_string();
+                self.with_items(|items| items.iter().position(|item| item.to_string() == text))
+            },
+            _ => match self.get_int_attribute(::attrs::VALUE) {
+                0 => None,
+                selected => Some(selected as usize - 1),
+            },
+        }
+    }
+
+    /// Get the indices of all selected items, in ascending order.
+    ///
+    /// In modes other than `ListMode::MultiSelect`, this contains `selected_index()` if it is
+    /// not `None`.
+    pub fn selected_indices(&self) -> Vec<usize> {
+        if self.mode() != ListMode::MultiSelect {
+            return self.selected_index().into_iter().collect();
+        }
+
+        // Multiple selection is a string with a `+` or `-` for each item.
+        self.get_str_attribute(::attrs::VALUE).map_or_else(Vec::new, |value|
+            value.bytes().enumerate()
+                .filter(|&(_, mark)| mark == b'+')
+                .map(|(idx, _)| idx)
+                .collect()
+        )
+    }
+
+    /// Get a copy of the selected item, if any. See `selected_index()` for which item this is.
+    pub fn selected_item(&self) -> Option<T> where T: Clone {
+        self.with_selected_item(|item| item.cloned())
+    }
+
+    /// Call `f` with the selected item, if any. See `selected_index()` for which item this is.
+    ///
+    /// The items are owned by the list rather than by this handle, so they can only be
+    /// borrowed for the duration of a call, like `with_items()`.
+    pub fn with_selected_item<F, R>(&self, f: F) -> R where F: FnOnce(Option<&T>) -> R {
+        let index = self.selected_index();
+        self.with_items(|items| f(index.and_then(|index| items.get(index))))
+    }
+
+    /// Select the item at `index`, or clear the selection if `None`. In
+    /// `ListMode::MultiSelect`, this replaces the current selection.
+    ///
+    /// Does not invoke any callbacks.
+    pub fn select(self, index: Option<usize>) -> Self {
+        match self.mode() {
+            ListMode::MultiSelect => return self.select_indices(index.as_slice()),
+            ListMode::ComboBox => {
+                let text = index.and_then(|index|
+                    self.with_items(|items| items.get(index).map(T::to_string))
+                );
+                self.set_str_attribute(::attrs::VALUE, text.unwrap_or_default());
+            },
+            _ => self.set_int_attribute(::attrs::VALUE, index.map_or(0, |index| index as i32 + 1)),
+        }
+
+        self
+    }
+
+    /// Select the items at the given indices, deselecting all others.
+    ///
+    /// Only selects the first index unless this list is in `ListMode::MultiSelect`.
+    pub fn select_indices(self, indices: &[usize]) -> Self {
+        if self.mode() != ListMode::MultiSelect {
+            return self.select(indices.first().cloned());
+        }
+
+        let marks: String = (0 .. self.len())
+            .map(|idx| if indices.contains(&idx) { '+' } else { '-' })
+            .collect();
+
+        self.set_str_attribute(::attrs::VALUE, marks);
+        self
+    }
+
+    /// Set the image displayed beside the item at `index`.
+    ///
+    /// ##Note
+    /// Images must be set before the list is shown for the first time, or they will not be
+    /// displayed. Not supported in `ListMode::ComboBox`.
+    pub fn set_item_image(self, index: usize, image: Image) -> Self {
+        self.set_bool_attribute(::attrs::SHOWIMAGE, true);
+
+        let name = CString::new(format!("IMAGE{}", index + 1)).unwrap();
+        unsafe { ::iup_sys::IupSetAttributeHandle(self.ptr(), name.as_ptr(), image.ptr()); }
+        self
+    }
+
+    /// Set a callback to be invoked with the index of an item when the user selects it, and
+    /// whether it was selected (`true`) or deselected (`false`).
+    pub fn set_on_item_action<Cb>(self, on_action: Cb) -> Self
+    where Cb: Callback<(Self, usize, bool)> {
+        let mut on_action = on_action;
+        let on_action = move |(list, item, selected): (BaseWidget, usize, bool)|
+            on_action.on_callback((unsafe { Self::from_ptr(list.ptr()) }, item, selected));
+
+        callback_impl! {
+            ::attrs::ACTION, self, on_action, List, (BaseWidget, usize, bool),
+            |element, _text: *mut c_char, item: c_int, state: c_int|
+                (BaseWidget::from_ptr(element), item as usize - 1, state == 1)
+        }
+        self
+    }
+
+    /// Set a callback to be invoked with the index of an item when the user double-clicks it.
+    ///
+    /// Not invoked in `ListMode::Dropdown` or `ListMode::ComboBox`.
+    pub fn set_on_double_click<Cb>(self, on_double_click: Cb) -> Self
+    where Cb: Callback<(Self, usize)> {
+        let mut on_double_click = on_double_click;
+        let on_double_click = move |(list, item): (BaseWidget, usize)|
+            on_double_click.on_callback((unsafe { Self::from_ptr(list.ptr()) }, item));
+
+        callback_impl! {
+            ::attrs::DBLCLICK_CB, self, on_double_click, List, (BaseWidget, usize),
+            |element, item: c_int, _text: *mut c_char|
+                (BaseWidget::from_ptr(element), item as usize - 1)
+        }
+        self
+    }
+}
+
+thread_local!(
+    static LIST_DATA: RefCell<HashMap<IUPPtr, Box<dyn Any>>> = RefCell::new(HashMap::new())
+);
+
+fn forget_list_data(widget: Option<IUPPtr>) {
+    LIST_DATA.with(|data| ::forget_state(data, widget));
+}
+
+impl_widget! { List<T> }
+
+// Invoked when the selection changes, or when the text changes in `ListMode::ComboBox`.
+impl_on_value_change! { List<T> }
+
+impl_on_tooltip! { List<T> }
+
+impl_on_key! { List<T> }
+
+impl_on_focus! { List<T> }
+
+impl_on_mouse_button! { List<T> }
+
+impl_on_mouse_move! { List<T> }
+
+impl_on_mouse_enter_leave! { List<T> }
+
+impl<T: 'static> ::dragdrop::DragSource for List<T> {}
+
+impl<T: 'static> ::dragdrop::DropTarget for List<T> {}
+
+/// A source of rows for a `VirtualList`.
+pub trait ListModel: 'static {
+    /// The number of rows.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The text of the row at `index`, which is always less than `len()`.
+    ///
+    /// Only called for rows that are being drawn, so it can be computed on demand.
+    fn row(&self, index: usize) -> Cow<'_, str>;
+}
+
+impl<S: AsRef<str> + 'static> ListModel for Vec<S> {
+    fn len(&self) -> usize {
+        Vec::len(self)
+    }
+
+    fn row(&self, index: usize) -> Cow<'_, str> {
+        Cow::Borrowed(self[index].as_ref())
+    }
+}
+
+/// A list which draws its rows itself, only asking its `ListModel` for the rows currently on
+/// screen. It can show hundreds of thousands of rows, where `List` would be slow to fill.
+///
+/// One row can be selected by clicking it or with the arrow, Page Up/Down, Home and End keys.
+///
+/// ##Note
+/// The list does not know when the model changes. After changing it through `with_model()`, call
+/// `rows_changed()`, `rows_inserted()`, `rows_removed()` or `reset()`. These only redraw the
+/// list if the affected rows are on screen.
+pub struct VirtualList<M>(IUPPtr, PhantomData<M>);
+
+type RowCallback = Rc<RefCell<Box<dyn FnMut(usize) -> CallbackStatus>>>;
+
+struct VirtualListState {
+    model: Rc<RefCell<dyn ListModel>>,
+    /// The same model as `Rc<RefCell<M>>`, for `with_model()`.
+    typed_model: Box<dyn Any>,
+    selected: Option<usize>,
+    /// The height of the visible area, in pixels.
+    height: i32,
+    on_selection_changed: Option<RowCallback>,
+    on_double_click: Option<RowCallback>,
+}
+
+thread_local!(
+    static VIRTUAL_LISTS: RefCell<HashMap<IUPPtr, VirtualListState>> = RefCell::new(HashMap::new())
+);
+
+fn forget_virtual_list(widget: Option<IUPPtr>) {
+    VIRTUAL_LISTS.with(|lists| ::forget_state(lists, widget));
+}
+
+const ROW_PADDING: i32 = 2;
+
+const SELECTION_COLOR: Color = Color::rgb(0, 120, 215);
+
+impl<M: ListModel> VirtualList<M> {
+    /// Create a new virtual list showing the rows of `model`.
+    pub fn new(model: M) -> VirtualList<M> {
+        let list = unsafe {
+            let ptr = iup_sys::IupCanvas(ptr::null());
+            Self::from_ptr(ptr)
+        };
+
+        list.set_const_str_attribute(::attrs::SCROLLBAR, cstr!("VERTICAL"));
+
+        let model = Rc::new(RefCell::new(model));
+
+        VIRTUAL_LISTS.with(|lists| lists.borrow_mut().insert(list.ptr(), VirtualListState {
+            model: model.clone(),
+            typed_model: Box::new(model),
+            selected: None,
+            height: 0,
+            on_selection_changed: None,
+            on_double_click: None,
+        }));
+        ::KISSContext::track_state(list.ptr(), forget_virtual_list);
+
+        let base = list.to_base();
+
+        callback_impl! {
+            ::attrs::ACTION, base, draw_rows, BaseWidget, BaseWidget,
+            |element, _posx: c_float, _posy: c_float| BaseWidget::from_ptr(element)
+        }
+
+        callback_impl! {
+            ::attrs::RESIZE_CB, base, resize, BaseWidget, (BaseWidget, i32),
+            |element, _width: c_int, height: c_int| (BaseWidget::from_ptr(element), height)
+        }
+
+        callback_impl! {
+            ::attrs::BUTTON_CB, base, click, BaseWidget, (BaseWidget, bool, i32, bool),
+            |element, button: c_int, pressed: c_int, _x: c_int, y: c_int, status: *mut c_char| (
+                BaseWidget::from_ptr(element),
+                button == iup_sys::IUP_BUTTON1 && pressed != 0,
+                y,
+                MouseStatus::from_ptr(status).double_click
+            )
+        }
+
+        callback_impl! {
+            key: ::attrs::K_ANY, base, navigate, BaseWidget, (BaseWidget, KeyEvent),
+            |element, c: c_int| (BaseWidget::from_ptr(element), KeyEvent::from_code(c))
+        }
+
+        list
+    }
+
+    /// Call `f` with mutable access to the model.
+    ///
+    /// The list is not redrawn afterwards; see the notes on `VirtualList`.
+    pub fn with_model<F, R>(&self, f: F) -> R where F: FnOnce(&mut M) -> R {
+        let model = with_state(self.to_base(), |state|
+            state.typed_model.downcast_ref::<Rc<RefCell<M>>>().cloned()
+        ).expect("Virtual list model was not registered!");
+
+        let mut model = model.borrow_mut();
+        f(&mut model)
+    }
+
+    /// Notify the list that the text of the rows in `rows` changed.
+    pub fn rows_changed(&self, rows: Range<usize>) {
+        redraw_rows(self.to_base(), rows);
+    }
+
+    /// Notify the list that `count` rows were inserted at `index`.
+    pub fn rows_inserted(&self, index: usize, count: usize) {
+        with_state(self.to_base(), |state| {
+            state.selected = state.selected.map(|selected|
+                if selected >= index { selected + count } else { selected }
+            )
+        });
+
+        self.rows_moved(index);
+    }
+
+    /// Notify the list that `count` rows were removed at `index`.
+    ///
+    /// If the selected row was removed, the selection is cleared without invoking the
+    /// selection callback.
+    pub fn rows_removed(&self, index: usize, count: usize) {
+        with_state(self.to_base(), |state| {
+            state.selected = state.selected.and_then(|selected|
+                if selected >= index + count {
+                    Some(selected - count)
+                } else if selected >= index {
+                    None
+                } else {
+                    Some(selected)
+                }
+            )
+        });
+
+        self.rows_moved(index);
+    }
+
+    fn rows_moved(&self, index: usize) {
+        let base = self.to_base();
+        update_scrollbar(base);
+
+        // Every row from `index` on moved, as did the end of the list.
+        redraw_rows(base, index .. usize::MAX);
+    }
+
+    /// Notify the list that the model was replaced or changed entirely.
+    ///
+    /// Clears the selection without invoking the selection callback, and scrolls to the top.
+    pub fn reset(&self) {
+        let base = self.to_base();
+        with_state(base, |state| state.selected = None);
+
+        self.set_double_attribute(::attrs::POSY, 0.0);
+        update_scrollbar(base);
+        redraw_rows(base, 0 .. usize::MAX);
+    }
+
+    /// Get the index of the selected row, if any.
+    pub fn selected(&self) -> Option<usize> {
+        with_state(self.to_base(), |state| state.selected)
+    }
+
+    /// Select the row at `index` and scroll it into view, or clear the selection if `None`.
+    ///
+    /// Does not invoke the selection callback.
+    pub fn select(self, index: Option<usize>) -> Self {
+        select_row(self.to_base(), index, false);
+        self
+    }
+
+    /// Set a callback to be invoked with the index of the selected row when the user selects
+    /// a different row.
+    pub fn set_on_selection_changed<Cb>(self, on_changed: Cb) -> Self
+    where Cb: Callback<(Self, usize)> {
+        let mut on_changed = on_changed;
+        let on_changed = move |row| on_changed.on_callback((self, row));
+
+        with_state(self.to_base(), |state|
+            state.on_selection_changed = Some(Rc::new(RefCell::new(Box::new(on_changed))))
+        );
+        self
+    }
+
+    /// Set a callback to be invoked with the index of a row when the user double-clicks it.
+    pub fn set_on_double_click<Cb>(self, on_double_click: Cb) -> Self
+    where Cb: Callback<(Self, usize)> {
+        let mut on_double_click = on_double_click;
+        let on_double_click = move |row| on_double_click.on_callback((self, row));
+
+        with_state(self.to_base(), |state|
+            state.on_double_click = Some(Rc::new(RefCell::new(Box::new(on_double_click))))
+        );
+        self
+    }
+}
+
+impl_widget! { VirtualList<M> }
+
+fn with_state<F, R>(list: BaseWidget, f: F) -> R where F: FnOnce(&mut VirtualListState) -> R {
+    VIRTUAL_LISTS.with(|lists|
+        f(lists.borrow_mut().get_mut(&list.ptr()).expect("Virtual list was not registered!"))
+    )
+}
+
+fn row_count(list: BaseWidget) -> usize {
+    let model = with_state(list, |state| state.model.clone());
+    let len = model.borrow().len();
+    len
+}
+
+fn row_height(list: BaseWidget) -> i32 {
+    cmp::max(TextMetrics::of(list).char_height as i32, 1) + ROW_PADDING * 2
+}
+
+fn visible_rows(list: BaseWidget) -> Range<usize> {
+    let row_height = row_height(list) as f64;
+    let top = list.get_double_attribute(::attrs::POSY);
+    let height = with_state(list, |state| state.height) as f64;
+
+    (top / row_height) as usize .. ((top + height) / row_height) as usize + 1
+}
+
+/// Repaint the list if any of the rows in `rows` are on screen.
+///
+/// IUP can only invalidate a canvas as a whole, so this is as specific as a redraw can be.
+fn redraw_rows(list: BaseWidget, rows: Range<usize>) {
+    let visible = visible_rows(list);
+
+    if rows.start < visible.end && visible.start < rows.end {
+        unsafe { iup_sys::IupUpdate(list.ptr()); }
+    }
+}
+
+/// The vertical extent of the area being repainted, if the system reports one.
+fn clip_rows(list: BaseWidget) -> Option<(i32, i32)> {
+    let rect = list.get_str_attribute(::attrs::CLIPRECT)?;
+    let coords: Vec<i32> = rect.split(' ').filter_map(|coord| coord.parse().ok()).collect();
+
+    match coords[..] {
+        [_, y1, _, y2] => Some((y1, y2)),
+        _ => None,
+    }
+}
+
+// Scroll positions are in pixels, which can exceed `i32` for a large model, so they are kept
+// as `f64` like IUP does.
+fn update_scrollbar(list: BaseWidget) {
+    let total = row_count(list) as f64 * row_height(list) as f64;
+    let height = with_state(list, |state| state.height) as f64;
+
+    list.set_double_attribute(::attrs::YMAX, total);
+    list.set_double_attribute(::attrs::DY, height);
+
+    let top = list.get_double_attribute(::attrs::POSY);
+    list.set_double_attribute(::attrs::POSY, top.min(total - height).max(0.0));
+}
+
+fn scroll_into_view(list: BaseWidget, index: usize) {
+    let row_height = row_height(list) as f64;
+    let row_top = index as f64 * row_height;
+    let top = list.get_double_attribute(::attrs::POSY);
+    let height = with_state(list, |state| state.height) as f64;
+
+    if row_top < top {
+        list.set_double_attribute(::attrs::POSY, row_top);
+    } else if row_top + row_height > top + height {
+        list.set_double_attribute(::attrs::POSY, row_top + row_height - height);
+    }
+}
+
+fn select_row(list: BaseWidget, index: Option<usize>, notify: bool) -> CallbackStatus {
+    let (previous, on_changed) = with_state(list, |state| {
+        let previous = state.selected;
+        state.selected = index;
+        (previous, state.on_selection_changed.clone())
+    });
+    let changed = previous != index;
+
+    let top = list.get_double_attribute(::attrs::POSY);
+
+    if let Some(index) = index {
+        scroll_into_view(list, index);
+    }
+
+    if list.get_double_attribute(::attrs::POSY) != top {
+        redraw_rows(list, 0 .. usize::MAX);
+    } else if changed {
+        for row in previous.into_iter().chain(index) {
+            redraw_rows(list, row .. row + 1);
+        }
+    }
+
+    match (index, on_changed) {
+        (Some(index), Some(on_changed)) if changed && notify => (*on_changed.borrow_mut())(index),
+        _ => CallbackStatus::Default,
+    }
+}
+
+fn set_draw_color(list: BaseWidget, color: Color) {
+    list.set_str_attribute(::attrs::DRAWCOLOR, color.to_string());
+}
+
+fn draw_rows(list: BaseWidget) {
+    let (model, selected) = with_state(list, |state| (state.model.clone(), state.selected));
+    let model = model.borrow();
+    let row_height = row_height(list);
+    let top = list.get_double_attribute(::attrs::POSY);
+
+    unsafe {
+        IupDrawBegin(list.ptr());
+
+        let (mut width, mut height) = (0, 0);
+        IupDrawGetSize(list.ptr(), &mut width, &mut height);
+
+        // Only the rows overlapping the damaged area need to be drawn, e.g. when part of the
+        // list is uncovered.
+        let (y1, y2) = clip_rows(list).unwrap_or((0, height - 1));
+
+        list.set_const_str_attribute(::attrs::DRAWSTYLE, cstr!("FILL"));
+        set_draw_color(list, Color::WHITE);
+        IupDrawRectangle(list.ptr(), 0, y1, width - 1, y2);
+
+        let first = ((top + cmp::max(y1, 0) as f64) / row_height as f64) as usize;
+        let last = cmp::min(model.len(), ((top + y2 as f64) / row_height as f64) as usize + 1);
+
+        for index in first .. last {
+            let y = (index as f64 * row_height as f64 - top) as i32;
+
+            if selected == Some(index) {
+                set_draw_color(list, SELECTION_COLOR);
+                IupDrawRectangle(list.ptr(), 0, y, width - 1, y + row_height - 1);
+                set_draw_color(list, Color::WHITE);
+            } else {
+                set_draw_color(list, Color::BLACK);
+            }
+
+            // The length is passed explicitly, so the text needs no terminator.
+            let row = model.row(index);
+            IupDrawText(
+                list.ptr(), row.as_ptr() as *const c_char, row.len() as c_int,
+                ROW_PADDING * 2, y + ROW_PADDING,
+                width - ROW_PADDING * 4, row_height - ROW_PADDING * 2
+            );
+        }
+
+        IupDrawEnd(list.ptr());
+    }
+}
+
+fn resize((list, height): (BaseWidget, i32)) {
+    with_state(list, |state| state.height = height);
+    update_scrollbar(list);
+}
+
+fn click((list, left_pressed, y, double_click): (BaseWidget, bool, i32, bool)) -> CallbackStatus {
+    if !left_pressed {
+        return CallbackStatus::Default;
+    }
+
+    let top = list.get_double_attribute(::attrs::POSY);
+    let index = ((y as f64 + top) / row_height(list) as f64) as usize;
+
+    if index >= row_count(list) {
+        return CallbackStatus::Default;
+    }
+
+    if double_click {
+        let on_double_click = with_state(list, |state| state.on_double_click.clone());
+        on_double_click.map_or(CallbackStatus::Default, |cb| (*cb.borrow_mut())(index))
+    } else {
+        select_row(list, Some(index), true)
+    }
+}
+
+fn navigate((list, key): (BaseWidget, KeyEvent)) -> CallbackStatus {
+    let len = row_count(list);
+
+    if len == 0 || !key.modifiers.is_empty() {
+        return CallbackStatus::Default;
+    }
+
+    let last = len - 1;
+    let page = cmp::max(with_state(list, |state| state.height) / row_height(list), 1) as usize;
+    let selected = with_state(list, |state| state.selected);
+
+    let index = match (key.key, selected) {
+        (Key::Up, Some(selected)) => selected.saturating_sub(1),
+        (Key::Down, Some(selected)) => cmp::min(selected + 1, last),
+        (Key::PageUp, Some(selected)) => selected.saturating_sub(page),
+        (Key::PageDown, Some(selected)) => cmp::min(selected + page, last),
+        (Key::Up, None) | (Key::Down, None) | (Key::PageUp, None) | (Key::PageDown, None) |
+        (Key::Home, _) => 0,
+        (Key::End, _) => last,
+        _ => return CallbackStatus::Default,
+    };
+
+    match select_row(list, Some(index), true) {
+        CallbackStatus::Default => CallbackStatus::Ignore,
+        status => status,
+    }
+}
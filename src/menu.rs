@@ -0,0 +1,208 @@
+//! Menus shown in the menu bar of a dialog, whose items can have keyboard shortcuts.
+//!
+//! ```ignore
+//! let menu = Menu::new(children![
+//!     Submenu::new("&File", Menu::new(children![
+//!         MenuItem::new("&Save").set_shortcut("Ctrl+S").unwrap().set_onclick(save),
+//!         Menu::separator(),
+//!         MenuItem::new("E&xit").set_onclick(|_| CallbackStatus::Close),
+//!     ])),
+//! ]);
+//!
+//! Dialog::new(contents).set_menu(menu).unwrap()
+//! ```
+
+use base::BaseWidget;
+use callback::{Callback, CallbackStatus, OnClick};
+use container::raw_handle_vec;
+use keyboard::{KeyEvent, ParseKeyError};
+use widget_prelude::*;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::ptr;
+use std::rc::Rc;
+
+/// A list of menu items, shown as the menu bar of a dialog or as the contents of a `Submenu`.
+///
+/// For more info, see the [`IupMenu`][iup-menu] documentation.
+///
+/// [iup-menu]: http://webserver2.tecgraf.puc-rio.br/iup/en/elem/iupmenu.html
+pub struct Menu(IUPPtr);
+
+impl Menu {
+    /// Create a menu of `items`, which may be `MenuItem`s, `Submenu`s and separators.
+    pub fn new<C>(items: C) -> Menu where C: AsRef<[BaseWidget]> {
+        let mut raw_handles = raw_handle_vec(items);
+
+        unsafe {
+            let ptr = ::iup_sys::IupMenuv(raw_handles.as_mut_ptr());
+            Self::from_ptr(ptr)
+        }
+    }
+
+    /// Create a line for dividing groups of items in a menu.
+    pub fn separator() -> BaseWidget {
+        unsafe { BaseWidget::from_ptr(::iup_sys::IupSeparator()) }
+    }
+
+    /// Get the items of this menu and its submenus with a shortcut set.
+    #[doc(hidden)]
+    pub fn shortcuts(self) -> Vec<(KeyEvent, MenuItem)> {
+        let mut shortcuts = Vec::new();
+        let mut child = unsafe { ::iup_sys::IupGetNextChild(self.ptr(), ptr::null_mut()) };
+
+        while let Some(base) = unsafe { BaseWidget::from_ptr_opt(child) } {
+            match base.classname().to_bytes() {
+                b"item" => {
+                    let item = unsafe { MenuItem::from_ptr(child) };
+                    shortcuts.extend(item.get_shortcut().map(|shortcut| (shortcut, item)));
+                },
+                b"submenu" => unsafe {
+                    if let Some(menu) = BaseWidget::from_ptr_opt(::iup_sys::IupGetChild(child, 0)) {
+                        shortcuts.extend(Menu::from_ptr(menu.ptr()).shortcuts());
+                    }
+                },
+                _ => (),
+            }
+
+            child = unsafe { ::iup_sys::IupGetNextChild(self.ptr(), child) };
+        }
+
+        shortcuts
+    }
+}
+
+impl_widget! { Menu, "menu" }
+
+/// An item in a `Menu` which invokes its `OnClick` callback when chosen.
+///
+/// For more info, see the [`IupItem`][iup-item] documentation.
+///
+/// [iup-item]: http://webserver2.tecgraf.puc-rio.br/iup/en/elem/iupitem.html
+pub struct MenuItem(IUPPtr);
+
+impl MenuItem {
+    /// Create a new menu item with the given label.
+    ///
+    /// A `&` before a character makes it the item's mnemonic, as with `Button::set_label()`.
+    pub fn new<L: Into<String>>(label: L) -> MenuItem {
+        let c_label = CString::new(label.into()).unwrap();
+
+        let item = unsafe {
+            let ptr = ::iup_sys::IupItem(c_label.as_ptr(), ptr::null());
+            Self::from_ptr(ptr)
+        };
+
+        item.set_callback(::attrs::ACTION, unsafe {
+            ::std::mem::transmute::<*const (), ::iup_sys::Icallback>(item_action as *const ())
+        });
+
+        item
+    }
+
+    /// Set the label of this item, keeping its shortcut.
+    pub fn set_label<L: Into<String>>(self, label: L) -> Self {
+        let shortcut = self.get_shortcut();
+        self.set_str_attribute(::attrs::TITLE, item_title(&label.into(), shortcut));
+        self
+    }
+
+    /// Get the label of this item, without its shortcut.
+    pub fn get_label(&self) -> String {
+        self.get_str_attribute(::attrs::TITLE)
+            .map_or_else(String::new, |title| split_title(&title).0.to_owned())
+    }
+
+    /// Set the keyboard shortcut of this item, e.g. `"Ctrl+S"`, which is shown beside its label.
+    /// See `keyboard::KeyEvent` for the accepted syntax.
+    ///
+    /// Pressing the shortcut chooses this item once its menu is set on a dialog with
+    /// `Dialog::set_menu()`, and while the item is enabled.
+    pub fn set_shortcut(self, shortcut: &str) -> Result<Self, ParseKeyError> {
+        let shortcut = shortcut.parse()?;
+        let label = self.get_label();
+        self.set_str_attribute(::attrs::TITLE, item_title(&label, Some(shortcut)));
+        Ok(self)
+    }
+
+    /// Get the keyboard shortcut of this item, if one is set.
+    pub fn get_shortcut(&self) -> Option<KeyEvent> {
+        let title = self.get_str_attribute(::attrs::TITLE)?;
+        split_title(&title).1.and_then(|shortcut| shortcut.parse().ok())
+    }
+
+    /// Invoke this item's `OnClick` callback, as if the user chose it.
+    ///
+    /// Does nothing if this item is disabled.
+    pub fn activate(self) -> CallbackStatus {
+        if self.get_str_attribute(::attrs::ACTIVE).is_some_and(|active| &*active == "NO") {
+            return CallbackStatus::Default;
+        }
+
+        let on_click = ITEM_ACTIONS.with(|actions| actions.borrow().get(&self.ptr()).cloned());
+        on_click.map_or(CallbackStatus::Default, |on_click| on_click.borrow_mut().on_callback(self))
+    }
+}
+
+impl_widget! { MenuItem, "item" }
+
+// The callbacks are kept here so shortcuts can invoke them through `activate()`.
+impl OnClick for MenuItem {
+    fn set_onclick<Cb>(self, on_click: Cb) -> Self where Cb: Callback<Self> {
+        ITEM_ACTIONS.with(|actions|
+            actions.borrow_mut().insert(self.ptr(), Rc::new(RefCell::new(Box::new(on_click))))
+        );
+        ::KISSContext::track_state(self.ptr(), forget_state);
+        self
+    }
+}
+
+type ItemCallback = Rc<RefCell<Box<dyn Callback<MenuItem>>>>;
+
+thread_local!(
+    static ITEM_ACTIONS: RefCell<HashMap<IUPPtr, ItemCallback>> = RefCell::new(HashMap::new())
+);
+
+fn forget_state(widget: Option<IUPPtr>) {
+    ITEM_ACTIONS.with(|actions| ::forget_state(actions, widget));
+}
+
+extern "C" fn item_action(element: *mut ::iup_sys::Ihandle) -> ::libc::c_int {
+    unsafe { MenuItem::from_ptr(element) }.activate().to_cb_return()
+}
+
+// IUP shows the text after a tab right-aligned in the item, as an accelerator.
+fn item_title(label: &str, shortcut: Option<KeyEvent>) -> String {
+    match shortcut {
+        Some(shortcut) => format!("{}\t{}", label, shortcut),
+        None => label.to_owned(),
+    }
+}
+
+fn split_title(title: &str) -> (&str, Option<&str>) {
+    let mut parts = title.splitn(2, '\t');
+    (parts.next().unwrap_or(""), parts.next())
+}
+
+/// An item in a `Menu` which opens another menu when chosen.
+///
+/// For more info, see the [`IupSubmenu`][iup-submenu] documentation.
+///
+/// [iup-submenu]: http://webserver2.tecgraf.puc-rio.br/iup/en/elem/iupsubmenu.html
+pub struct Submenu(IUPPtr);
+
+impl Submenu {
+    /// Create a new submenu with the given label, which opens `menu`.
+    pub fn new<L: Into<String>>(label: L, menu: Menu) -> Submenu {
+        let c_label = CString::new(label.into()).unwrap();
+
+        unsafe {
+            let ptr = ::iup_sys::IupSubmenu(c_label.as_ptr(), menu.ptr());
+            Self::from_ptr(ptr)
+        }
+    }
+}
+
+impl_widget! { Submenu, "submenu" }
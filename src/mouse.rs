@@ -0,0 +1,174 @@
+//! Mouse input: buttons, motion, wheel and modifier state.
+//!
+//! For more info, see the [IUP `BUTTON_CB`][iup-button-cb] documentation.
+//!
+//! [iup-button-cb]: http://webserver2.tecgraf.puc-rio.br/iup/en/call/iup_button_cb.html
+
+use keyboard::Modifiers;
+
+use std::ffi::CStr;
+
+use libc::c_char;
+
+/// A button on the mouse.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MouseButton {
+    Left,
+    Middle,
+    Right,
+    /// The first extra button, usually "back".
+    Extra1,
+    /// The second extra button, usually "forward".
+    Extra2,
+}
+
+impl MouseButton {
+    #[doc(hidden)]
+    pub fn from_code(code: i32) -> Option<MouseButton> {
+        use self::MouseButton::*;
+
+        match code {
+            ::iup_sys::IUP_BUTTON1 => Some(Left),
+            ::iup_sys::IUP_BUTTON2 => Some(Middle),
+            ::iup_sys::IUP_BUTTON3 => Some(Right),
+            ::iup_sys::IUP_BUTTON4 => Some(Extra1),
+            ::iup_sys::IUP_BUTTON5 => Some(Extra2),
+            _ => None,
+        }
+    }
+}
+
+/// The state of the modifier keys and mouse buttons during a mouse event.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct MouseStatus {
+    pub modifiers: Modifiers,
+    /// If the event is part of a double-click.
+    pub double_click: bool,
+    pub left: bool,
+    pub middle: bool,
+    pub right: bool,
+    pub extra1: bool,
+    pub extra2: bool,
+}
+
+impl MouseStatus {
+    /// Decode the status string IUP passes to mouse callbacks.
+    ///
+    /// Each flag has a fixed position in the string, which holds a space if the flag is not set.
+    pub fn parse(status: &[u8]) -> MouseStatus {
+        let flag = |idx: usize, set: u8| status.get(idx) == Some(&set);
+
+        MouseStatus {
+            modifiers: Modifiers {
+                shift: flag(0, b'S'),
+                ctrl: flag(1, b'C'),
+                alt: flag(6, b'A'),
+                sys: flag(7, b'Y'),
+            },
+            left: flag(2, b'1'),
+            middle: flag(3, b'2'),
+            right: flag(4, b'3'),
+            double_click: flag(5, b'D'),
+            extra1: flag(8, b'4'),
+            extra2: flag(9, b'5'),
+        }
+    }
+
+    #[doc(hidden)]
+    pub unsafe fn from_ptr(status: *const c_char) -> MouseStatus {
+        if status.is_null() {
+            MouseStatus::default()
+        } else {
+            MouseStatus::parse(CStr::from_ptr(status).to_bytes())
+        }
+    }
+
+    /// Returns `true` if `button` is held.
+    pub fn is_held(&self, button: MouseButton) -> bool {
+        use self::MouseButton::*;
+
+        match button {
+            Left => self.left,
+            Middle => self.middle,
+            Right => self.right,
+            Extra1 => self.extra1,
+            Extra2 => self.extra2,
+        }
+    }
+}
+
+/// A mouse button was pressed or released over a widget.
+///
+/// Coordinates are in pixels relative to the top-left corner of the widget.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MouseButtonEvent {
+    pub button: MouseButton,
+    /// `true` if the button was pressed, `false` if it was released.
+    pub pressed: bool,
+    pub x: i32,
+    pub y: i32,
+    pub status: MouseStatus,
+}
+
+impl MouseButtonEvent {
+    /// Returns `true` if this press is the second click of a double-click.
+    pub fn is_double_click(&self) -> bool {
+        self.status.double_click
+    }
+}
+
+/// The mouse moved over a widget.
+///
+/// Coordinates are in pixels relative to the top-left corner of the widget.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct MouseMoveEvent {
+    pub x: i32,
+    pub y: i32,
+    pub status: MouseStatus,
+}
+
+/// The mouse wheel was rotated over a widget.
+///
+/// Coordinates are in pixels relative to the top-left corner of the widget.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct MouseWheelEvent {
+    /// The number of notches the wheel was rotated; positive away from the user.
+    pub delta: f32,
+    pub x: i32,
+    pub y: i32,
+    pub status: MouseStatus,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MouseButton, MouseStatus};
+
+    use std::ffi::CString;
+    use std::ptr;
+
+    #[test]
+    fn parse_status() {
+        let status = MouseStatus::parse(b"SC1 3DAY45");
+
+        assert!(status.modifiers.shift && status.modifiers.ctrl);
+        assert!(status.modifiers.alt && status.modifiers.sys);
+        assert!(status.double_click);
+        assert!(status.is_held(MouseButton::Left) && status.is_held(MouseButton::Right));
+        assert!(!status.is_held(MouseButton::Middle));
+        assert!(status.is_held(MouseButton::Extra1) && status.is_held(MouseButton::Extra2));
+    }
+
+    #[test]
+    fn parse_empty_status() {
+        assert_eq!(MouseStatus::parse(b"          "), MouseStatus::default());
+        // Flags are positional, so a letter in the wrong place is not set.
+        assert_eq!(MouseStatus::parse(b"1"), MouseStatus::default());
+        assert_eq!(MouseStatus::parse(b"  2"), MouseStatus::default());
+
+        assert_eq!(unsafe { MouseStatus::from_ptr(ptr::null()) }, MouseStatus::default());
+
+        let status = CString::new(" C 2").unwrap();
+        let status = unsafe { MouseStatus::from_ptr(status.as_ptr()) };
+        assert!(status.modifiers.ctrl && status.middle && !status.left);
+    }
+}
@@ -88,3 +88,5 @@ impl ProgressBar {
 
 impl_widget! { ProgressBar, "progressbar" }
 
+impl_on_tooltip! { ProgressBar }
+
@@ -0,0 +1,238 @@
+//! Sliders for choosing a value in a range by dragging a handle.
+
+use widget_prelude::*;
+
+use callback::{Callback, CallbackStatus};
+use container::Orientation;
+
+use libc::{c_char, c_int};
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// A widget with a handle the user can drag along a track to choose a value in a range.
+///
+/// The range is `0.0` to `1.0` by default.
+///
+/// For more info, see the [`IupVal`][iup-val] documentation.
+///
+/// [iup-val]: http://webserver2.tecgraf.puc-rio.br/iup/en/elem/iupval.html
+pub struct Slider(IUPPtr);
+
+type ValueCallback = Rc<RefCell<Box<dyn Callback<(Slider, f64)>>>>;
+
+#[derive(Default)]
+struct SliderState {
+    dragging: bool,
+    /// The step sizes in value units; IUP takes them as fractions of the range.
+    step: Option<f64>,
+    page_step: Option<f64>,
+    on_changing: Option<ValueCallback>,
+    on_changed: Option<ValueCallback>,
+}
+
+thread_local!(
+    static SLIDERS: RefCell<HashMap<IUPPtr, SliderState>> = RefCell::new(HashMap::new())
+);
+
+fn forget_state(widget: Option<IUPPtr>) {
+    SLIDERS.with(|sliders| ::forget_state(sliders, widget));
+}
+
+impl Slider {
+    /// Create a new slider with the given orientation.
+    ///
+    /// A vertical slider has its minimum at the bottom, unless inverted.
+    pub fn new(orientation: Orientation) -> Slider {
+        let slider = unsafe {
+            let ptr = ::iup_sys::IupVal(orientation.as_cstr().as_ptr() as *const c_char);
+            Self::from_ptr(ptr)
+        };
+
+        SLIDERS.with(|sliders| sliders.borrow_mut().insert(slider.ptr(), SliderState::default()));
+        ::KISSContext::track_state(slider.ptr(), forget_state);
+
+        callback_impl! { ::attrs::VALUE_CHANGED_CB, slider, value_changed, Slider }
+
+        callback_impl! {
+            ::attrs::VALUECHANGING_CB, slider, drag_changed, Slider, (Slider, bool),
+            |element, start: c_int| (Slider::from_ptr(element), start != 0)
+        }
+
+        slider
+    }
+
+    /// Set the range of values, inclusive.
+    ///
+    /// ##Panics
+    /// If `min` is not less than `max`.
+    pub fn set_range(self, min: f64, max: f64) -> Self {
+        assert!(min < max, "Slider minimum {} is not less than maximum {}", min, max);
+
+        self.set_str_attribute(::attrs::MIN, min.to_string());
+        self.set_str_attribute(::attrs::MAX, max.to_string());
+        self.update_steps();
+        self
+    }
+
+    /// Set the amount the value changes by when the user presses an arrow key.
+    ///
+    /// Defaults to 1% of the range.
+    pub fn set_step(self, step: f64) -> Self {
+        with_state(self, |state| state.step = Some(step));
+        self.update_steps();
+        self
+    }
+
+    /// Set the amount the value changes by when the user presses Page Up or Page Down, or
+    /// clicks the track.
+    ///
+    /// Defaults to 10% of the range.
+    pub fn set_page_step(self, page_step: f64) -> Self {
+        with_state(self, |state| state.page_step = Some(page_step));
+        self.update_steps();
+        self
+    }
+
+    fn update_steps(&self) {
+        let (min, max) = (self.get_min(), self.get_max());
+        let (step, page_step) = with_state(*self, |state| (state.step, state.page_step));
+
+        if let Some(step) = step {
+            self.set_str_attribute(::attrs::STEP, step_fraction(step, min, max).to_string());
+        }
+
+        if let Some(page_step) = page_step {
+            let page_step = step_fraction(page_step, min, max);
+            self.set_str_attribute(::attrs::PAGESTEP, page_step.to_string());
+        }
+    }
+
+    /// Get the minimum value.
+    pub fn get_min(&self) -> f64 {
+        self.get_f64_attribute(::attrs::MIN)
+    }
+
+    /// Get the maximum value.
+    pub fn get_max(&self) -> f64 {
+        self.get_f64_attribute(::attrs::MAX)
+    }
+
+    /// Set the number of tick marks shown along the track, or `0` for none.
+    ///
+    /// The marks are spaced evenly over the range, including both ends.
+    pub fn set_ticks(self, count: u32) -> Self {
+        self.set_int_attribute(::attrs::SHOWTICKS, count as i32);
+        self
+    }
+
+    /// Set if the minimum should be at the right of a horizontal slider, or the top of a
+    /// vertical slider.
+    pub fn set_inverted(self, inverted: bool) -> Self {
+        // IUP inverts vertical sliders by default, putting their minimum at the bottom.
+        let vertical = self.get_str_attribute(::attrs::ORIENTATION)
+            .is_some_and(|orientation| orientation.eq_ignore_ascii_case("VERTICAL"));
+
+        self.set_bool_attribute(::attrs::INVERTED, inverted != vertical);
+        self
+    }
+
+    /// Get the current value.
+    pub fn value(&self) -> f64 {
+        self.get_f64_attribute(::attrs::VALUE)
+    }
+
+    /// Set the current value, clamped to the range.
+    ///
+    /// Does not invoke any callbacks.
+    pub fn set_value(self, value: f64) -> Self {
+        self.set_str_attribute(::attrs::VALUE, value.to_string());
+        self
+    }
+
+    /// Set a callback to be invoked with the current value while the user drags the handle.
+    pub fn set_on_value_changing<Cb>(self, on_changing: Cb) -> Self
+    where Cb: Callback<(Self, f64)> {
+        with_state(self, |state|
+            state.on_changing = Some(Rc::new(RefCell::new(Box::new(on_changing))))
+        );
+        self
+    }
+
+    /// Set a callback to be invoked with the new value when the user has finished changing it,
+    /// by releasing the handle or with the keyboard.
+    pub fn set_on_value_changed<Cb>(self, on_changed: Cb) -> Self
+    where Cb: Callback<(Self, f64)> {
+        with_state(self, |state|
+            state.on_changed = Some(Rc::new(RefCell::new(Box::new(on_changed))))
+        );
+        self
+    }
+
+    // Doubles are formatted with full precision by IUP, unlike `get_float_attribute()`.
+    fn get_f64_attribute(&self, name: &'static str) -> f64 {
+        self.get_str_attribute(name)
+            .and_then(|val| val.parse().ok())
+            .unwrap_or(0.0)
+    }
+}
+
+impl_widget! { Slider, "val" }
+
+impl_on_tooltip! { Slider }
+
+impl_on_key! { Slider }
+
+impl_on_focus! { Slider }
+
+impl_on_mouse_enter_leave! { Slider }
+
+fn with_state<F, R>(slider: Slider, f: F) -> R where F: FnOnce(&mut SliderState) -> R {
+    SLIDERS.with(|sliders|
+        f(sliders.borrow_mut().entry(slider.ptr()).or_insert_with(SliderState::default))
+    )
+}
+
+/// Convert a step in value units to the fraction of the range IUP expects.
+fn step_fraction(step: f64, min: f64, max: f64) -> f64 {
+    step / (max - min)
+}
+
+fn notify(slider: Slider, callback: Option<ValueCallback>) -> CallbackStatus {
+    callback.map_or(CallbackStatus::Default, |callback|
+        callback.borrow_mut().on_callback((slider, slider.value()))
+    )
+}
+
+// IUP reports every change here, including each step of a drag.
+fn value_changed(slider: Slider) -> CallbackStatus {
+    let callback = with_state(slider, |state|
+        if state.dragging { state.on_changing.clone() } else { state.on_changed.clone() }
+    );
+
+    notify(slider, callback)
+}
+
+fn drag_changed((slider, start): (Slider, bool)) -> CallbackStatus {
+    let on_changed = with_state(slider, |state| {
+        state.dragging = start;
+        state.on_changed.clone()
+    });
+
+    // The value is committed when the drag ends.
+    if start { CallbackStatus::Default } else { notify(slider, on_changed) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::step_fraction;
+
+    #[test]
+    fn step_fractions() {
+        assert_eq!(step_fraction(1.0, 0.0, 100.0), 0.01);
+        assert_eq!(step_fraction(5.0, -10.0, 10.0), 0.25);
+        assert_eq!(step_fraction(0.5, 1.0, 2.0), 0.5);
+        assert_eq!(step_fraction(20.0, 0.0, 10.0), 2.0);
+    }
+}
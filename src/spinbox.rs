@@ -0,0 +1,363 @@
+//! Text boxes for entering numbers, with buttons to step the value up and down.
+
+use widget_prelude::*;
+
+use base::BaseWidget;
+use callback::{Callback, CallbackStatus};
+
+use libc::c_int;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::ptr;
+use std::rc::Rc;
+
+/// A primitive number type which can be the value of a `SpinBox`.
+pub trait Num: Copy + PartialOrd + Display + 'static {
+    /// If this type only holds whole numbers.
+    const INTEGER: bool;
+
+    fn to_f64(self) -> f64;
+
+    /// Convert from `f64`, rounding to the nearest value if this type is an integer.
+    fn from_f64(val: f64) -> Self;
+}
+
+macro_rules! impl_num {
+    (integer: $($ty:ident),+) => ($(
+        impl Num for $ty {
+            const INTEGER: bool = true;
+
+            fn to_f64(self) -> f64 { self as f64 }
+
+            fn from_f64(val: f64) -> $ty { val.round() as $ty }
+        }
+    )+);
+    (decimal: $($ty:ident),+) => ($(
+        impl Num for $ty {
+            const INTEGER: bool = false;
+
+            fn to_f64(self) -> f64 { self as f64 }
+
+            fn from_f64(val: f64) -> $ty { val as $ty }
+        }
+    )+);
+}
+
+impl_num! { integer: i8, i16, i32, i64, isize, u8, u16, u32, u64, usize }
+
+impl_num! { decimal: f32, f64 }
+
+/// A text box holding a number of type `T`, with spin buttons to step it up and down.
+///
+/// The value is kept between a minimum and maximum, 0 and 100 by default. Decimal types are
+/// shown with 2 digits after the decimal point by default; see `set_precision()`.
+///
+/// For more info, see the `SPIN` attributes in the [`IupText`][iup-text] documentation.
+///
+/// [iup-text]: http://webserver2.tecgraf.puc-rio.br/iup/en/elem/iuptext.html
+pub struct SpinBox<T>(IUPPtr, PhantomData<T>);
+
+type ValueCallback = Rc<RefCell<Box<dyn FnMut(f64) -> CallbackStatus>>>;
+
+/// If `min` and `max`, scaled by `precision`, fit in the `i32` values IUP spins between.
+fn fits_spin(min: f64, max: f64, precision: usize) -> bool {
+    let fits = |val: f64| {
+        let scaled = (val * 10f64.powi(precision as i32)).round();
+        scaled >= i32::MIN as f64 && scaled <= i32::MAX as f64
+    };
+
+    fits(min) && fits(max)
+}
+
+struct SpinState {
+    min: f64,
+    max: f64,
+    step: f64,
+    precision: usize,
+    /// The last value set or reported to the callback.
+    value: f64,
+    on_changed: Option<ValueCallback>,
+}
+
+impl SpinState {
+    /// IUP spins over integers, so decimal values are scaled by the precision.
+    fn scale(&self) -> f64 {
+        10f64.powi(self.precision as i32)
+    }
+
+    fn to_spin(&self, val: f64) -> i32 {
+        (val * self.scale()).round() as i32
+    }
+
+    fn clamp(&self, val: f64) -> f64 {
+        val.max(self.min).min(self.max)
+    }
+
+    fn in_range(&self, val: f64) -> bool {
+        self.clamp(val) == val
+    }
+
+    fn format(&self, val: f64) -> String {
+        format!("{:.*}", self.precision, val)
+    }
+}
+
+thread_local!(
+    static SPIN_BOXES: RefCell<HashMap<IUPPtr, SpinState>> = RefCell::new(HashMap::new())
+);
+
+fn forget_state(widget: Option<IUPPtr>) {
+    SPIN_BOXES.with(|spin_boxes| ::forget_state(spin_boxes, widget));
+}
+
+impl<T: Num> Default for SpinBox<T> {
+    fn default() -> SpinBox<T> {
+        SpinBox::new()
+    }
+}
+
+impl<T: Num> SpinBox<T> {
+    /// Create a new spin box with a value of zero.
+    pub fn new() -> SpinBox<T> {
+        let spin_box = unsafe {
+            let ptr = ::iup_sys::IupText(ptr::null());
+            Self::from_ptr(ptr)
+        };
+
+        spin_box.set_bool_attribute(::attrs::SPIN, true);
+        // Stepping is done in `spin()` so the value can be formatted with its precision.
+        spin_box.set_bool_attribute(::attrs::SPINAUTO, false);
+
+        SPIN_BOXES.with(|spin_boxes| spin_boxes.borrow_mut().insert(spin_box.ptr(), SpinState {
+            min: 0.0,
+            max: 100.0,
+            step: 1.0,
+            precision: if T::INTEGER { 0 } else { 2 },
+            value: 0.0,
+            on_changed: None,
+        }));
+        ::KISSContext::track_state(spin_box.ptr(), forget_state);
+
+        let base = spin_box.to_base();
+
+        callback_impl! {
+            ::attrs::SPIN_CB, base, spin, BaseWidget, (BaseWidget, i32),
+            |element, pos: c_int| (BaseWidget::from_ptr(element), pos)
+        }
+
+        callback_impl! { ::attrs::VALUE_CHANGED_CB, base, text_changed, BaseWidget }
+
+        spin_box.update();
+        spin_box
+    }
+
+    /// Set the range of values, inclusive. The current value is clamped to the new range.
+    ///
+    /// ##Panics
+    /// If `min` is greater than `max`, or if either, multiplied by 10 to the power of the
+    /// precision, does not fit in an `i32`.
+    pub fn set_range(self, min: T, max: T) -> Self {
+        assert!(min <= max, "SpinBox minimum {} is greater than maximum {}", min, max);
+
+        with_state(self.to_base(), |state| {
+            assert!(fits_spin(min.to_f64(), max.to_f64(), state.precision),
+                "SpinBox range {} to {} is too large for a precision of {}",
+                min, max, state.precision);
+
+            state.min = min.to_f64();
+            state.max = max.to_f64();
+            state.value = state.clamp(state.value);
+        });
+
+        self.update();
+        self
+    }
+
+    /// Set the amount the value changes by when a spin button is clicked.
+    pub fn set_step(self, step: T) -> Self {
+        with_state(self.to_base(), |state| state.step = step.to_f64());
+        self.update();
+        self
+    }
+
+    /// Set the number of digits shown after the decimal point, which the value is rounded to.
+    ///
+    /// Has no effect if `T` is an integer type.
+    ///
+    /// ##Panics
+    /// If the minimum or maximum, multiplied by 10 to the power of `digits`, does not fit in an
+    /// `i32`.
+    pub fn set_precision(self, digits: u32) -> Self {
+        if !T::INTEGER {
+            with_state(self.to_base(), |state| {
+                assert!(fits_spin(state.min, state.max, digits as usize),
+                    "SpinBox precision {} is too large for the range {} to {}",
+                    digits, state.min, state.max);
+
+                state.precision = digits as usize;
+            });
+            self.update();
+        }
+
+        self
+    }
+
+    /// Get the current value.
+    ///
+    /// If the text cannot be parsed as a number, or is out of range, this is the last valid
+    /// value, so it is always within the range.
+    pub fn value(&self) -> T {
+        let base = self.to_base();
+        let parsed = parse_value(base);
+
+        let value = with_state(base, |state|
+            parsed.filter(|&value| state.in_range(value)).unwrap_or(state.value)
+        );
+        T::from_f64(value)
+    }
+
+    /// Set the current value, clamped to the range.
+    ///
+    /// Does not invoke the change callback.
+    pub fn set_value(self, value: T) -> Self {
+        with_state(self.to_base(), |state| state.value = state.clamp(value.to_f64()));
+        self.update();
+        self
+    }
+
+    /// Set a callback to be invoked with the new value when the user changes it, either by
+    /// typing a valid number or with the spin buttons.
+    pub fn set_on_value_changed<Cb>(self, on_changed: Cb) -> Self
+    where Cb: Callback<(Self, T)> {
+        let mut on_changed = on_changed;
+        let on_changed = move |value| on_changed.on_callback((self, T::from_f64(value)));
+
+        with_state(self.to_base(), |state|
+            state.on_changed = Some(Rc::new(RefCell::new(Box::new(on_changed))))
+        );
+        self
+    }
+
+    fn update(&self) {
+        let (text, min, max, step, value) = with_state(self.to_base(), |state| (
+            state.format(state.value),
+            state.to_spin(state.min),
+            state.to_spin(state.max),
+            state.to_spin(state.step),
+            state.to_spin(state.value),
+        ));
+
+        self.set_int_attribute(::attrs::SPINMIN, min);
+        self.set_int_attribute(::attrs::SPINMAX, max);
+        self.set_int_attribute(::attrs::SPININC, step);
+        self.set_int_attribute(::attrs::SPINVALUE, value);
+        self.set_str_attribute(::attrs::VALUE, text);
+    }
+}
+
+impl_widget! { SpinBox<T> }
+
+fn with_state<F, R>(spin_box: BaseWidget, f: F) -> R where F: FnOnce(&mut SpinState) -> R {
+    SPIN_BOXES.with(|spin_boxes|
+        f(spin_boxes.borrow_mut().get_mut(&spin_box.ptr()).expect("SpinBox was not registered!"))
+    )
+}
+
+fn parse_value(spin_box: BaseWidget) -> Option<f64> {
+    let text = spin_box.get_str_attribute(::attrs::VALUE)?;
+    text.trim().parse().ok()
+}
+
+/// Store `value` and invoke the change callback if it differs from the last value.
+fn value_changed(spin_box: BaseWidget, value: f64) -> CallbackStatus {
+    let on_changed = with_state(spin_box, |state| {
+        if state.value == value {
+            return None;
+        }
+
+        state.value = value;
+        state.on_changed.clone()
+    });
+
+    on_changed.map_or(CallbackStatus::Default, |on_changed| (*on_changed.borrow_mut())(value))
+}
+
+fn spin((spin_box, pos): (BaseWidget, i32)) -> CallbackStatus {
+    let (value, text) = with_state(spin_box, |state| {
+        let value = state.clamp(pos as f64 / state.scale());
+        (value, state.format(value))
+    });
+
+    spin_box.set_str_attribute(::attrs::VALUE, text);
+    value_changed(spin_box, value)
+}
+
+fn text_changed(spin_box: BaseWidget) -> CallbackStatus {
+    let value = match parse_value(spin_box) {
+        Some(value) => value,
+        None => return CallbackStatus::Default,
+    };
+
+    // Out of range values are kept in the text, but not reported, until they are corrected.
+    let (in_range, spin_value) = with_state(spin_box, |state|
+        (state.in_range(value), state.to_spin(value))
+    );
+
+    if !in_range {
+        return CallbackStatus::Default;
+    }
+
+    spin_box.set_int_attribute(::attrs::SPINVALUE, spin_value);
+    value_changed(spin_box, value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fits_spin, SpinState};
+
+    fn state(precision: usize) -> SpinState {
+        SpinState {
+            min: -10.0,
+            max: 10.0,
+            step: 0.25,
+            precision,
+            value: 0.0,
+            on_changed: None,
+        }
+    }
+
+    #[test]
+    fn scaling() {
+        let integer = state(0);
+        assert_eq!(integer.scale(), 1.0);
+        assert_eq!(integer.to_spin(4.6), 5);
+        assert_eq!(integer.format(4.0), "4");
+
+        let decimal = state(2);
+        assert_eq!(decimal.scale(), 100.0);
+        assert_eq!(decimal.to_spin(0.25), 25);
+        assert_eq!(decimal.to_spin(-1.005), -100);
+        assert_eq!(decimal.format(1.5), "1.50");
+    }
+
+    #[test]
+    fn spin_bounds() {
+        assert!(fits_spin(-10.0, 10.0, 8));
+        assert!(!fits_spin(-10.0, 10.0, 9));
+        assert!(!fits_spin(0.0, i32::MAX as f64 + 1.0, 0));
+    }
+
+    #[test]
+    fn range() {
+        let state = state(2);
+        assert_eq!(state.clamp(11.0), 10.0);
+        assert_eq!(state.clamp(-11.0), -10.0);
+        assert_eq!(state.clamp(3.0), 3.0);
+
+        assert!(state.in_range(10.0) && state.in_range(-10.0));
+        assert!(!state.in_range(10.01));
+    }
+}
@@ -2,7 +2,20 @@
 
 use widget_prelude::*;
 
+use base::BaseWidget;
+use callback::Callback;
+use color::Color;
+use container::{Expand, HAlign, Horizontal, Orientation, VAlign, Vertical};
+use dialog::Dialog;
+use font::Font;
+use image::{Image, ImageContainer, ImagePosition};
+use keyboard::KeyEvent;
+use utils::cstr::AsCStr;
+
+use libc::c_int;
+
 use std::ffi::CString;
+use std::ops::Range;
 use std::ptr;
 
 /// A static widget that renders text within its parent.
@@ -28,6 +41,10 @@ impl Label {
 
     /// Update the text of this label.
     ///
+    /// A `&` before a character makes it the label's mnemonic: it will be underlined, and
+    /// pressing Alt and that character will focus the widget after the label, or the one set
+    /// with `set_mnemonic_target()`. Use `&&` for a literal `&`.
+    ///
     /// ##Panics
     /// If any `WidgetStr` instances from `self.get_text()` are still reachable.
     pub fn set_text(self, text: &str) -> Self {
@@ -40,12 +57,148 @@ impl Label {
         self.get_str_attribute(::attrs::TITLE)
             .expect("This widget should have a text pointer even if it's empty!")
     }
+
+    /// Create a line for dividing groups of widgets, running in the given direction.
+    ///
+    /// The line fills the available space in its direction, unlike other labels.
+    pub fn separator(orientation: Orientation) -> Label {
+        let separator = Label::new_empty();
+        separator.set_const_str_attribute(::attrs::SEPARATOR, orientation.as_cstr());
+
+        match orientation {
+            Orientation::Horizontal => separator.set_expand(Expand::Horizontal),
+            Orientation::Vertical => separator.set_expand(Expand::Vertical),
+        }
+    }
+
+    /// Create an image with text beside it, e.g. for an icon next to a heading, with `spacing`
+    /// pixels between them.
+    ///
+    /// A single label shows either an image or text, so this returns a container with one of
+    /// each.
+    pub fn with_image<S: Into<String>>(image: Image, text: S, position: ImagePosition,
+                                       spacing: u32) -> BaseWidget {
+        use self::ImagePosition::*;
+
+        let image = Label::new_empty().set_image(image).to_base();
+        let text = Label::new(text).to_base();
+
+        match position {
+            Left => Horizontal::new([image, text])
+                .set_valign(VAlign::Center).set_elem_spacing_pixels(spacing).to_base(),
+            Right => Horizontal::new([text, image])
+                .set_valign(VAlign::Center).set_elem_spacing_pixels(spacing).to_base(),
+            Top => Vertical::new([image, text])
+                .set_halign(HAlign::Center).set_elem_spacing_pixels(spacing).to_base(),
+            Bottom => Vertical::new([text, image])
+                .set_halign(HAlign::Center).set_elem_spacing_pixels(spacing).to_base(),
+        }
+    }
+
+    /// Set the alignment of the text or image within the label's area, if it is larger than
+    /// the contents.
+    pub fn set_alignment(self, horizontal: HAlign, vertical: VAlign) -> Self {
+        let alignment = format!(
+            "{}:{}",
+            horizontal.as_cstr().trim_end_matches('\0'),
+            vertical.as_cstr().trim_end_matches('\0')
+        );
+
+        self.set_str_attribute(::attrs::ALIGNMENT, alignment);
+        self
+    }
+
+    /// Set if the text should wrap onto more lines when it is wider than the label.
+    ///
+    /// The label's width should be limited, e.g. with `Widget::set_user_size()`, or it will
+    /// grow to fit the text.
+    pub fn set_wordwrap(self, wordwrap: bool) -> Self {
+        self.set_bool_attribute(::attrs::WORDWRAP, wordwrap);
+        self
+    }
+
+    /// Set if text wider than the label should be cut off with "..." at its end.
+    pub fn set_ellipsis(self, ellipsis: bool) -> Self {
+        self.set_bool_attribute(::attrs::ELLIPSIS, ellipsis);
+        self
+    }
+
+    /// Set if the text should be parsed as Pango markup, e.g. `"<b>Bold</b> and <i>italic</i>"`.
+    ///
+    /// ##Note
+    /// Only supported on GTK; other platforms show the markup as plain text.
+    pub fn set_markup(self, markup: bool) -> Self {
+        self.set_bool_attribute(::attrs::MARKUP, markup);
+        self
+    }
+
+    /// Set the widget to be focused when this label's mnemonic is pressed, instead of the widget
+    /// following it in the dialog.
+    ///
+    /// Typically used to associate a label with the input it describes.
+    pub fn set_mnemonic_target<W: Widget>(self, target: W) -> Self {
+        // Stored by IUP on the label, so it is freed with the label and forgotten if the target
+        // is destroyed first.
+        self.set_attr_handle(::attrs::MNEMONIC_TARGET, target);
+        self
+    }
+}
+
+/// Find the target of a label in `dialog` whose mnemonic matches `key`, for use when dispatching
+/// the dialog's key events.
+#[doc(hidden)]
+pub fn mnemonic_target(dialog: Dialog, key: KeyEvent) -> Option<BaseWidget> {
+    let key_char = match key.key {
+        ::keyboard::Key::Char(c) if key.modifiers.alt && !key.modifiers.ctrl => c,
+        _ => return None,
+    };
+
+    find_mnemonic_target(dialog.to_base(), key_char)
+}
+
+// Only the live descendants of the dialog are visited.
+fn find_mnemonic_target(parent: BaseWidget, key_char: char) -> Option<BaseWidget> {
+    let mut child = unsafe { ::iup_sys::IupGetNextChild(parent.ptr(), ptr::null_mut()) };
+
+    while let Some(widget) = unsafe { BaseWidget::from_ptr_opt(child) } {
+        let target = if widget.classname().to_bytes() == b"label" {
+            widget.get_attr_handle(::attrs::MNEMONIC_TARGET).filter(|_| {
+                let label = unsafe { Label::from_ptr(child) };
+                let mnemonic = ::keyboard::mnemonic(&label.get_text());
+                mnemonic.is_some_and(|c| c.to_lowercase().eq(key_char.to_lowercase()))
+            })
+        } else {
+            find_mnemonic_target(widget, key_char)
+        };
+
+        if target.is_some() {
+            return target;
+        }
+
+        child = unsafe { ::iup_sys::IupGetNextChild(parent.ptr(), child) };
+    }
+
+    None
 }
 
 impl_widget! { Label, "label" }
 
+impl_on_tooltip! { Label }
+
+impl_on_mouse_button! { Label }
+
+impl_on_mouse_enter_leave! { Label }
+
+impl_on_drop_files! { Label }
+
+impl ::dragdrop::DragSource for Label {}
+
+impl ::dragdrop::DropTarget for Label {}
+
 impl ::image::ImageContainer for Label {}
 
+impl ::container::Padding for Label {}
+
 /// A widget that renders user-editable text.
 pub struct TextBox(IUPPtr);
 
@@ -102,7 +255,527 @@ impl TextBox {
     }    
 }
 
+/// Editing. All positions are offsets in characters (not bytes or UTF-16 units) from the start
+/// of the text, and lines and columns are counted from zero.
+impl TextBox {
+    /// Set if the user is prevented from editing the text. It can still be selected and copied.
+    pub fn set_readonly(self, readonly: bool) -> Self {
+        self.set_bool_attribute(::attrs::READONLY, readonly);
+        self
+    }
+
+    /// Set if the text should be hidden behind placeholder characters as it is typed.
+    ///
+    /// ##Note
+    /// Has no effect after the text box is shown for the first time.
+    pub fn set_password(self, password: bool) -> Self {
+        self.set_bool_attribute(::attrs::PASSWORD, password);
+        self
+    }
+
+    /// Set the maximum number of characters the user can enter, or `None` for no limit.
+    pub fn set_max_length(self, max_length: Option<usize>) -> Self {
+        self.set_int_attribute(::attrs::NC, max_length.map_or(0, |max_length| max_length as i32));
+        self
+    }
+
+    /// Get the range of the selected text, if any.
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let selection = self.get_str_attribute(::attrs::SELECTIONPOS)?;
+        let mut bounds = selection.split(':').map(|pos| pos.trim().parse::<usize>());
+
+        let text = self.get_text();
+
+        match (bounds.next(), bounds.next()) {
+            (Some(Ok(start)), Some(Ok(end))) if start < end =>
+                Some(from_iup_pos(&text, start) .. from_iup_pos(&text, end)),
+            _ => None,
+        }
+    }
+
+    /// Select the text in `range`, or clear the selection if `range` is empty.
+    pub fn select(self, range: Range<usize>) -> Self {
+        if range.start < range.end {
+            let selection = iup_range(&self.get_text(), range);
+            self.set_str_attribute(::attrs::SELECTIONPOS, selection);
+        } else {
+            self.set_const_str_attribute(::attrs::SELECTIONPOS, cstr!("NONE"));
+        }
+
+        self
+    }
+
+    /// Get the position of the caret.
+    pub fn caret(&self) -> usize {
+        from_iup_pos(&self.get_text(), self.get_int_attribute(::attrs::CARETPOS) as usize)
+    }
+
+    /// Move the caret to `pos`, clamped to the end of the text.
+    pub fn set_caret(self, pos: usize) -> Self {
+        let pos = to_iup_pos(&self.get_text(), pos);
+        self.set_int_attribute(::attrs::CARETPOS, pos as i32);
+        self
+    }
+
+    /// Get the position of the caret as `(line, column)`.
+    pub fn caret_line_col(&self) -> (usize, usize) {
+        pos_to_line_col(&self.get_text(), self.caret())
+    }
+
+    /// Move the caret to `column` in `line`, clamped to the end of the line and of the text.
+    pub fn set_caret_line_col(self, line: usize, column: usize) -> Self {
+        let pos = line_col_to_pos(&self.get_text(), line, column);
+        self.set_caret(pos)
+    }
+
+    /// Insert `text` at the caret, replacing the selected text if there is any.
+    ///
+    /// ##Panics
+    /// If any `WidgetStr` instances from `self.get_text()` are still reachable.
+    pub fn insert_at_caret(self, text: &str) -> Self {
+        ::KISSContext::assert_str_not_borrowed(self.ptr(), ::attrs::VALUE);
+        self.set_str_attribute(::attrs::INSERT, text);
+        self
+    }
+
+    /// Add `text` to the end of the text. No newline is added before it.
+    ///
+    /// ##Panics
+    /// If any `WidgetStr` instances from `self.get_text()` are still reachable.
+    pub fn append(self, text: &str) -> Self {
+        ::KISSContext::assert_str_not_borrowed(self.ptr(), ::attrs::VALUE);
+        self.set_bool_attribute(::attrs::APPENDNEWLINE, false);
+        self.set_str_attribute(::attrs::APPEND, text);
+        self
+    }
+
+    /// Scroll the text so the character at `pos` is visible. Does not move the caret.
+    pub fn scroll_to(self, pos: usize) -> Self {
+        let pos = to_iup_pos(&self.get_text(), pos);
+        self.set_int_attribute(::attrs::SCROLLTOPOS, pos as i32);
+        self
+    }
+
+    /// Get the number of lines of text, which is always `1` if not multiline.
+    pub fn line_count(&self) -> usize {
+        self.get_int_attribute(::attrs::LINECOUNT) as usize
+    }
+
+    /// Set a callback to be invoked when the caret moves, with its new position and its
+    /// position as line and column: `(self, pos, line, column)`.
+    pub fn set_on_caret_moved<Cb>(self, on_caret_moved: Cb) -> Self
+    where Cb: Callback<(Self, usize, usize, usize)> {
+        callback_impl! {
+            ::attrs::CARET_CB, self, on_caret_moved, TextBox, (TextBox, usize, usize, usize),
+            |element, _lin: c_int, _col: c_int, pos: c_int| {
+                // The line and column are recomputed, as IUP counts columns like positions.
+                let text_box = TextBox::from_ptr(element);
+                let text = text_box.get_text();
+                let pos = from_iup_pos(&text, pos as usize);
+                let (line, column) = pos_to_line_col(&text, pos);
+                (text_box, pos, line, column)
+            }
+        }
+        self
+    }
+}
+
+/// Input filtering.
+impl TextBox {
+    /// Restrict the text the user can type or paste to what matches `mask`, or remove any mask
+    /// if `None`.
+    ///
+    /// The current text is not checked. Use `OnEdit::set_on_edit()` for checks which are not
+    /// possible with a pattern.
+    pub fn set_mask(self, mask: Option<Mask>) -> Self {
+        let separator = ::get_global(::attrs::DEFAULT_DECIMAL_SYMBOL)
+            .and_then(|symbol| symbol.chars().next())
+            .unwrap_or('.');
+
+        self.set_opt_str_attribute(::attrs::MASK, mask.map(|mask| mask.into_pattern(separator)));
+        self
+    }
+}
+
+impl ::callback::OnEdit for TextBox {}
+
+/// A pattern that restricts what the user can enter in a `TextBox`.
+///
+/// For more info, see the [IUP mask syntax][iup-mask] documentation.
+///
+/// [iup-mask]: http://webserver2.tecgraf.puc-rio.br/iup/en/attrib/iup_mask.html
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Mask {
+    /// Digits only.
+    UInt,
+    /// Digits with an optional leading sign.
+    Int,
+    /// A decimal number without a sign.
+    ///
+    /// The decimal separator is the system's, or `.` if IUP cannot tell; see
+    /// `with_decimal_separator()`.
+    UFloat,
+    /// A decimal number with an optional leading sign, with the same separator as `UFloat`.
+    Float,
+    /// Like `Float`, with an optional exponent, e.g. `1.5e-3`.
+    EFloat,
+    /// A pattern in the IUP mask syntax, e.g. `"/d/d/d/d-/d/d-/d/d"` for a date.
+    ///
+    /// If the pattern is invalid, no mask is set.
+    Custom(String),
+}
+
+impl Mask {
+    /// Use `separator` in the decimal presets instead of the system's decimal separator, e.g.
+    /// for numbers that are not localized. Other masks are returned unchanged.
+    pub fn with_decimal_separator(self, separator: char) -> Mask {
+        match self {
+            Mask::UFloat | Mask::Float | Mask::EFloat => Mask::Custom(self.into_pattern(separator)),
+            mask => mask,
+        }
+    }
+
+    fn into_pattern(self, separator: char) -> String {
+        use self::Mask::*;
+
+        let pattern = match self {
+            UInt => return ::iup_sys::IUP_MASK_UINT.into(),
+            Int => return ::iup_sys::IUP_MASK_INT.into(),
+            Custom(pattern) => return pattern,
+            UFloat => ::iup_sys::IUP_MASK_UFLOAT,
+            Float => ::iup_sys::IUP_MASK_FLOAT,
+            EFloat => ::iup_sys::IUP_MASK_EFLOAT,
+        };
+
+        if separator == '.' {
+            pattern.into()
+        } else {
+            pattern.replace("/.", &format!("[{}]", separator))
+        }
+    }
+}
+
+/// Formatted text.
+impl TextBox {
+    /// Set if this text box can show formatted text, applied with `format_range()`,
+    /// `format_selection()` and `add_formatted()`.
+    ///
+    /// ##Note
+    /// Only supported in multiline text boxes, and has no effect after the text box is shown for
+    /// the first time.
+    pub fn set_formatting(self, formatting: bool) -> Self {
+        self.set_bool_attribute(::attrs::FORMATTING, formatting);
+        self
+    }
+
+    /// Apply `format` to the text in `range`.
+    ///
+    /// Formats applied before the text box is shown are kept until it is shown.
+    pub fn format_range(self, range: Range<usize>, format: &TextFormat) -> Self {
+        format.apply(self, Some(range));
+        self
+    }
+
+    /// Apply `format` to the selected text.
+    pub fn format_selection(self, format: &TextFormat) -> Self {
+        format.apply(self, None);
+        self
+    }
+
+    /// Add `text` to the end of the text, with `format` applied to it. No newline is added
+    /// before it.
+    ///
+    /// ##Panics
+    /// If any `WidgetStr` instances from `self.get_text()` are still reachable.
+    pub fn add_formatted(self, text: &str, format: &TextFormat) -> Self {
+        let start = self.get_text().chars().count();
+        self.append(text).format_range(start .. start + text.chars().count(), format)
+    }
+
+    /// Remove all formatting from the text.
+    pub fn clear_formatting(self) -> Self {
+        self.set_const_str_attribute(::attrs::REMOVEFORMATTING, cstr!("ALL"));
+        self
+    }
+}
+
+/// The alignment of a paragraph of formatted text. See `TextFormat::alignment()`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ParagraphAlignment {
+    Left,
+    Right,
+    Center,
+    Justify,
+}
+
+impl ParagraphAlignment {
+    fn as_cstr(self) -> &'static str {
+        use self::ParagraphAlignment::*;
+
+        match self {
+            Left => cstr!("LEFT"),
+            Right => cstr!("RIGHT"),
+            Center => cstr!("CENTER"),
+            Justify => cstr!("JUSTIFY"),
+        }
+    }
+}
+
+/// A builder for the format of a run of text in a `TextBox` with formatting enabled.
+///
+/// Options which are not set keep their current value in the formatted text.
+///
+/// For more info, see the [IUP text formatting][iup-format] documentation.
+///
+/// [iup-format]: http://webserver2.tecgraf.puc-rio.br/iup/en/attrib/iup_formatting.html
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TextFormat {
+    pub fg_color: Option<Color>,
+    pub bg_color: Option<Color>,
+    pub face: Option<String>,
+    pub size: Option<i32>,
+    pub bold: Option<bool>,
+    pub italic: Option<bool>,
+    pub underline: Option<bool>,
+    pub strikeout: Option<bool>,
+    /// Applies to whole paragraphs.
+    pub alignment: Option<ParagraphAlignment>,
+    /// The indentation of each paragraph, in pixels. Applies to whole paragraphs.
+    pub indent: Option<u32>,
+}
+
+impl TextFormat {
+    pub fn new() -> TextFormat {
+        TextFormat::default()
+    }
+
+    pub fn fg_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.fg_color = Some(color.into());
+        self
+    }
+
+    pub fn bg_color<C: Into<Color>>(mut self, color: C) -> Self {
+        self.bg_color = Some(color.into());
+        self
+    }
+
+    /// Set the typeface, size and style flags from `font`. A size of `0` leaves the size
+    /// unchanged.
+    pub fn font(mut self, font: &Font) -> Self {
+        self.face = Some(font.face.clone());
+
+        if font.size != 0 {
+            self.size = Some(font.size);
+        }
+
+        self.bold = Some(font.bold);
+        self.italic = Some(font.italic);
+        self.underline = Some(font.underline);
+        self.strikeout = Some(font.strikeout);
+        self
+    }
+
+    pub fn bold(mut self, bold: bool) -> Self {
+        self.bold = Some(bold);
+        self
+    }
+
+    pub fn italic(mut self, italic: bool) -> Self {
+        self.italic = Some(italic);
+        self
+    }
+
+    pub fn underline(mut self, underline: bool) -> Self {
+        self.underline = Some(underline);
+        self
+    }
+
+    pub fn strikeout(mut self, strikeout: bool) -> Self {
+        self.strikeout = Some(strikeout);
+        self
+    }
+
+    pub fn alignment(mut self, alignment: ParagraphAlignment) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+
+    pub fn indent(mut self, indent: u32) -> Self {
+        self.indent = Some(indent);
+        self
+    }
+
+    fn apply(&self, text_box: TextBox, range: Option<Range<usize>>) {
+        use attrs::values::bool_yes_no;
+
+        // Format tags are `IupUser` elements holding the attributes to apply.
+        let tag = unsafe { BaseWidget::from_ptr(::iup_sys::IupUser()) };
+
+        if let Some(range) = range {
+            tag.set_str_attribute(::attrs::SELECTIONPOS, iup_range(&text_box.get_text(), range));
+        }
+
+        if let Some(color) = self.fg_color {
+            tag.set_str_attribute(::attrs::FG_COLOR, color.to_string());
+        }
+
+        if let Some(color) = self.bg_color {
+            tag.set_str_attribute(::attrs::BG_COLOR, color.to_string());
+        }
+
+        if let Some(ref face) = self.face {
+            tag.set_str_attribute(::attrs::FONTFACE, &face[..]);
+        }
+
+        if let Some(size) = self.size {
+            tag.set_int_attribute(::attrs::FONTSIZE, size);
+        }
+
+        if let Some(bold) = self.bold {
+            let weight = if bold { cstr!("BOLD") } else { cstr!("NORMAL") };
+            tag.set_const_str_attribute(::attrs::WEIGHT, weight);
+        }
+
+        if let Some(italic) = self.italic {
+            tag.set_const_str_attribute(::attrs::ITALIC, bool_yes_no(italic));
+        }
+
+        if let Some(underline) = self.underline {
+            let underline = if underline { cstr!("SINGLE") } else { cstr!("NONE") };
+            tag.set_const_str_attribute(::attrs::UNDERLINE, underline);
+        }
+
+        if let Some(strikeout) = self.strikeout {
+            tag.set_const_str_attribute(::attrs::STRIKEOUT, bool_yes_no(strikeout));
+        }
+
+        if let Some(alignment) = self.alignment {
+            tag.set_const_str_attribute(::attrs::ALIGNMENT, alignment.as_cstr());
+        }
+
+        if let Some(indent) = self.indent {
+            tag.set_int_attribute(::attrs::INDENT, indent as i32);
+        }
+
+        // IUP takes ownership of the tag and destroys it once it is applied.
+        unsafe {
+            ::iup_sys::IupSetAttribute(
+                text_box.ptr(), ::attrs::ADDFORMATTAG_HANDLE.as_cstr(), tag.ptr() as *const _
+            );
+        }
+    }
+}
+
+// With `UTF8MODE`, which `show_gui()` always sets, IUP takes and returns text as UTF-8 but
+// positions in it are still counted natively: in UTF-16 units on Windows and in characters
+// elsewhere.
+fn to_iup_pos(text: &str, pos: usize) -> usize {
+    if cfg!(windows) { chars_to_utf16(text, pos) } else { pos }
+}
+
+fn from_iup_pos(text: &str, pos: usize) -> usize {
+    if cfg!(windows) { utf16_to_chars(text, pos) } else { pos }
+}
+
+fn iup_range(text: &str, range: Range<usize>) -> String {
+    format!("{}:{}", to_iup_pos(text, range.start), to_iup_pos(text, range.end))
+}
+
+fn chars_to_utf16(text: &str, pos: usize) -> usize {
+    text.chars().take(pos).map(char::len_utf16).sum()
+}
+
+fn utf16_to_chars(text: &str, pos: usize) -> usize {
+    let mut units = 0;
+    text.chars().take_while(|c| { units += c.len_utf16(); units <= pos }).count()
+}
+
+fn pos_to_line_col(text: &str, pos: usize) -> (usize, usize) {
+    text.chars().take(pos).fold((0, 0), |(line, column), c|
+        if c == '\n' { (line + 1, 0) } else { (line, column + 1) }
+    )
+}
+
+fn line_col_to_pos(text: &str, line: usize, column: usize) -> usize {
+    let mut lines = text.split('\n');
+
+    let before: usize = lines.by_ref().take(line).map(|line| line.chars().count() + 1).sum();
+    let line_len = lines.next().map_or(0, |line| line.chars().count());
+
+    ::std::cmp::min(before, text.chars().count()) + ::std::cmp::min(column, line_len)
+}
+
 impl_widget! { TextBox, "text" }
 
 impl_on_value_change! { TextBox }
 
+impl_on_tooltip! { TextBox }
+
+impl_on_key! { TextBox }
+
+impl_on_focus! { TextBox }
+
+impl_on_mouse_button! { TextBox }
+
+impl_on_mouse_move! { TextBox }
+
+impl_on_mouse_enter_leave! { TextBox }
+
+impl_on_drop_files! { TextBox }
+
+impl ::dragdrop::DragSource for TextBox {}
+
+impl ::dragdrop::DropTarget for TextBox {}
+
+impl ::container::Padding for TextBox {}
+
+#[cfg(test)]
+mod tests {
+    use super::{chars_to_utf16, line_col_to_pos, pos_to_line_col, utf16_to_chars, Mask};
+
+    const TEXT: &str = "h\u{e9}llo\nw\u{f6}rld \u{1f600}!";
+
+    #[test]
+    fn line_col_counts_characters() {
+        assert_eq!(pos_to_line_col(TEXT, 2), (0, 2));
+        assert_eq!(pos_to_line_col(TEXT, 8), (1, 2));
+        assert_eq!(pos_to_line_col(TEXT, 13), (1, 7));
+
+        assert_eq!(line_col_to_pos(TEXT, 1, 2), 8);
+        assert_eq!(line_col_to_pos(TEXT, 1, 7), 13);
+        // Clamped to the end of the line and of the text.
+        assert_eq!(line_col_to_pos(TEXT, 0, 10), 5);
+        assert_eq!(line_col_to_pos(TEXT, 5, 0), 14);
+    }
+
+    #[test]
+    fn utf16_positions() {
+        // Everything before the emoji is one unit per character; the emoji takes two.
+        assert_eq!(chars_to_utf16(TEXT, 12), 12);
+        assert_eq!(chars_to_utf16(TEXT, 13), 14);
+        assert_eq!(chars_to_utf16(TEXT, 100), 15);
+
+        assert_eq!(utf16_to_chars(TEXT, 12), 12);
+        assert_eq!(utf16_to_chars(TEXT, 14), 13);
+        assert_eq!(utf16_to_chars(TEXT, 15), 14);
+    }
+
+    #[test]
+    fn mask_patterns() {
+        assert_eq!(Mask::UInt.into_pattern(','), "/d+");
+        assert_eq!(Mask::Int.into_pattern('.'), "[+/-]?/d+");
+        assert_eq!(Mask::Float.into_pattern('.'), "[+/-]?(/d+/.?/d*|/./d+)");
+        assert_eq!(Mask::UFloat.into_pattern(','), "(/d+[,]?/d*|[,]/d+)");
+        assert_eq!(Mask::Custom("/d/d".into()).into_pattern(','), "/d/d");
+    }
+
+    #[test]
+    fn mask_decimal_separator_override() {
+        let comma = Mask::EFloat.with_decimal_separator(',');
+        assert_eq!(comma, Mask::Custom("[+/-]?(/d+[,]?/d*|[,]/d+)([eE][+/-]?/d+)?".into()));
+        // The override is kept whatever the system separator is.
+        assert_eq!(comma.clone().into_pattern('.'), comma.into_pattern('\''));
+
+        assert_eq!(Mask::Int.with_decimal_separator(','), Mask::Int);
+    }
+}
@@ -0,0 +1,117 @@
+//! Tooltips shown when the mouse hovers over a widget.
+//!
+//! For more info, see the [IUP `TIP` attribute][iup-tip] documentation.
+//!
+//! [iup-tip]: http://webserver2.tecgraf.puc-rio.br/iup/en/attrib/iup_tip.html
+
+use widget_prelude::*;
+
+/// The icon shown next to the title of a balloon tooltip.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum TooltipIcon {
+    None,
+    Info,
+    Warning,
+    Error,
+}
+
+impl TooltipIcon {
+    fn as_int(self) -> i32 {
+        use self::TooltipIcon::*;
+
+        match self {
+            None => 0,
+            Info => 1,
+            Warning => 2,
+            Error => 3,
+        }
+    }
+}
+
+/// A builder for a tooltip with options beyond its text.
+///
+/// Set on a widget with `Widget::set_tooltip()`. A plain string can be passed instead when no
+/// other options are needed.
+#[derive(Clone, Debug)]
+pub struct Tooltip {
+    pub text: String,
+    pub balloon: Option<bool>,
+    pub title: Option<String>,
+    pub icon: Option<TooltipIcon>,
+    pub delay_ms: Option<u32>,
+}
+
+impl Tooltip {
+    pub fn new<T: Into<String>>(text: T) -> Tooltip {
+        Tooltip {
+            text: text.into(),
+            balloon: None,
+            title: None,
+            icon: None,
+            delay_ms: None,
+        }
+    }
+
+    /// Render the tooltip as a speech balloon instead of a plain box.
+    ///
+    /// Only has an effect on Windows.
+    pub fn balloon(mut self, balloon: bool) -> Self {
+        self.balloon = Some(balloon);
+        self
+    }
+
+    /// Set the title of a balloon tooltip.
+    pub fn title<T: Into<String>>(mut self, title: T) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    /// Set the icon shown next to the title of a balloon tooltip.
+    pub fn icon(mut self, icon: TooltipIcon) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Set how long the tooltip stays visible, in milliseconds.
+    ///
+    /// Defaults to 5000.
+    pub fn delay_ms(mut self, delay_ms: u32) -> Self {
+        self.delay_ms = Some(delay_ms);
+        self
+    }
+
+    // Only the options set explicitly are written, so setting just the text from an
+    // `OnTooltip` callback keeps the balloon options set before.
+    #[doc(hidden)]
+    pub fn apply<W: Widget>(self, widget: W) {
+        widget.set_str_attribute(::attrs::TIP, self.text);
+
+        if let Some(balloon) = self.balloon {
+            widget.set_bool_attribute(::attrs::TIP_BALLOON, balloon);
+        }
+
+        if let Some(title) = self.title {
+            widget.set_str_attribute(::attrs::TIP_BALLOON_TITLE, title);
+        }
+
+        if let Some(icon) = self.icon {
+            widget.set_int_attribute(::attrs::TIP_BALLOON_TITLE_ICON, icon.as_int());
+        }
+
+        if let Some(delay_ms) = self.delay_ms {
+            widget.set_int_attribute(::attrs::TIP_DELAY, delay_ms as i32);
+        }
+    }
+}
+
+impl<'a> From<&'a str> for Tooltip {
+    fn from(text: &'a str) -> Tooltip {
+        Tooltip::new(text)
+    }
+}
+
+impl From<String> for Tooltip {
+    fn from(text: String) -> Tooltip {
+        Tooltip::new(text)
+    }
+}
@@ -0,0 +1,531 @@
+//! Validation of text entered in forms, with errors shown on the invalid fields.
+//!
+//! ```ignore
+//! let validator = Validator::new()
+//!     .field("Name", name, vec![Rule::required()])
+//!     .field("Age", age, vec![Rule::min(0), Rule::max(150)])
+//!     .field("Email", email, vec![Rule::required(), Rule::email()])
+//!     .form_rule(confirm, move || if confirm.get_text() == password.get_text() {
+//!         Ok(())
+//!     } else {
+//!         Err("The passwords do not match".into())
+//!     })
+//!     .bind_submit(ok_button);
+//! ```
+
+use widget_prelude::*;
+
+use button::Button;
+use color::Color;
+use text::{Label, TextBox};
+
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::fmt::{self, Display, Formatter};
+use std::rc::Rc;
+use std::str::FromStr;
+
+type CheckFn = Rc<dyn Fn(&str) -> Result<(), String>>;
+
+type FormCheckFn = Rc<dyn Fn() -> Result<(), String>>;
+
+/// A check on the text of a single field.
+///
+/// All rules except `required()` accept empty text, so optional fields can be left blank.
+#[derive(Clone)]
+pub struct Rule {
+    check: CheckFn,
+    skip_empty: bool,
+}
+
+impl Rule {
+    /// Create a rule from a function which returns an error message if `text` is invalid.
+    pub fn custom<F>(check: F) -> Rule where F: Fn(&str) -> Result<(), String> + 'static {
+        Rule { check: Rc::new(check), skip_empty: true }
+    }
+
+    /// The field must not be empty or only whitespace.
+    pub fn required() -> Rule {
+        let required = Rule::custom(|text|
+            if text.trim().is_empty() { Err("This field is required".into()) } else { Ok(()) }
+        );
+
+        Rule { skip_empty: false, ..required }
+    }
+
+    /// The field must have at least `min` characters.
+    pub fn min_length(min: usize) -> Rule {
+        Rule::custom(move |text|
+            if text.chars().count() < min {
+                Err(format!("Must be at least {} characters", min))
+            } else {
+                Ok(())
+            }
+        )
+    }
+
+    /// The field must have at most `max` characters.
+    pub fn max_length(max: usize) -> Rule {
+        Rule::custom(move |text|
+            if text.chars().count() > max {
+                Err(format!("Must be at most {} characters", max))
+            } else {
+                Ok(())
+            }
+        )
+    }
+
+    /// The field must be a number of type `T` no less than `min`.
+    pub fn min<T>(min: T) -> Rule where T: FromStr + PartialOrd + Display + 'static {
+        Rule::custom(move |text| match text.trim().parse::<T>() {
+            Ok(ref val) if *val < min => Err(format!("Must be at least {}", min)),
+            Ok(_) => Ok(()),
+            Err(_) => Err("Must be a number".into()),
+        })
+    }
+
+    /// The field must be a number of type `T` no greater than `max`.
+    pub fn max<T>(max: T) -> Rule where T: FromStr + PartialOrd + Display + 'static {
+        Rule::custom(move |text| match text.trim().parse::<T>() {
+            Ok(ref val) if *val > max => Err(format!("Must be at most {}", max)),
+            Ok(_) => Ok(()),
+            Err(_) => Err("Must be a number".into()),
+        })
+    }
+
+    /// The field must match a pattern, checked by `is_match`; otherwise `message` is the error.
+    ///
+    /// There is no regular expression rule, since KISS-UI depends only on `libc` and `iup-sys`.
+    /// A matcher from a crate such as `regex` can be used here instead, e.g.
+    /// `Rule::pattern("Must be a product code", move |text| regex.is_match(text))`.
+    pub fn pattern<M, F>(message: M, is_match: F) -> Rule
+    where M: Into<String>, F: Fn(&str) -> bool + 'static {
+        let message = message.into();
+        Rule::custom(move |text| if is_match(text) { Ok(()) } else { Err(message.clone()) })
+    }
+
+    /// The field must look like an email address: `name@domain.tld` without spaces.
+    pub fn email() -> Rule {
+        Rule::pattern("Must be an email address", |text| {
+            let text = text.trim();
+            let mut parts = text.splitn(2, '@');
+
+            match (parts.next(), parts.next()) {
+                (Some(name), Some(domain)) =>
+                    !name.is_empty() && !domain.contains('@')
+                        && !text.contains(char::is_whitespace)
+                        && domain.split('.').count() > 1
+                        && domain.split('.').all(|part| !part.is_empty()),
+                _ => false,
+            }
+        })
+    }
+
+    fn check(&self, text: &str) -> Result<(), String> {
+        if self.skip_empty && text.is_empty() {
+            Ok(())
+        } else {
+            (self.check)(text)
+        }
+    }
+}
+
+/// An error found by a `Validator`.
+#[derive(Clone)]
+pub struct ValidationError {
+    /// The name of the field, as passed to `Validator::field()`.
+    pub field: String,
+    pub text_box: TextBox,
+    pub message: String,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        write!(fmt, "{}: {}", self.field, self.message)
+    }
+}
+
+/// The result of validating a form: every error found, in the order the fields were added.
+#[derive(Clone, Default)]
+pub struct ValidationReport {
+    pub errors: Vec<ValidationError>,
+}
+
+impl ValidationReport {
+    /// Returns `true` if no errors were found.
+    pub fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Get the errors for `text_box`.
+    pub fn errors_for(&self, text_box: TextBox) -> Vec<&ValidationError> {
+        self.errors.iter().filter(|error| error.text_box.ptr() == text_box.ptr()).collect()
+    }
+}
+
+/// One error per line.
+impl Display for ValidationReport {
+    fn fmt(&self, fmt: &mut Formatter) -> fmt::Result {
+        for error in &self.errors {
+            writeln!(fmt, "{}", error)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The validators of a field, and the `OnValueChange` callback it had before it was added to
+/// any of them.
+struct FieldWatchers {
+    validators: Vec<Validator>,
+    previous: Option<::iup_sys::Icallback>,
+}
+
+thread_local!(
+    static FIELD_WATCHERS: RefCell<HashMap<IUPPtr, FieldWatchers>> = RefCell::new(HashMap::new())
+);
+
+// A field may be added to several validators, or to the same one twice, so a single callback
+// refreshes all of them instead of each chaining to the callback set before it.
+fn watch_field(text_box: TextBox, validator: Validator) {
+    let is_watched = FIELD_WATCHERS.with(|watchers| {
+        let mut watchers = watchers.borrow_mut();
+
+        match watchers.get_mut(&text_box.ptr()) {
+            Some(field) => {
+                if !field.validators.iter().any(|existing| Rc::ptr_eq(&existing.0, &validator.0)) {
+                    field.validators.push(validator.clone());
+                }
+                true
+            },
+            None => false,
+        }
+    });
+
+    if is_watched {
+        return;
+    }
+
+    let changed = unsafe {
+        ::std::mem::transmute::<*const (), ::iup_sys::Icallback>(field_changed as *const ())
+    };
+
+    let previous = text_box.get_callback(::attrs::VALUE_CHANGED_CB)
+        .filter(|&previous| previous as usize != changed as usize);
+
+    FIELD_WATCHERS.with(|watchers|
+        watchers.borrow_mut().insert(text_box.ptr(), FieldWatchers {
+            validators: vec![validator],
+            previous,
+        })
+    );
+    ::KISSContext::track_state(text_box.ptr(), forget_field_watchers);
+
+    text_box.set_callback(::attrs::VALUE_CHANGED_CB, changed);
+}
+
+fn forget_field_watchers(widget: Option<IUPPtr>) {
+    FIELD_WATCHERS.with(|watchers| ::forget_state(watchers, widget));
+}
+
+extern "C" fn field_changed(element: *mut ::iup_sys::Ihandle) -> ::libc::c_int {
+    let watchers = FIELD_WATCHERS.with(|watchers|
+        watchers.borrow().get(&element).map(|field| (field.validators.clone(), field.previous))
+    );
+
+    let (validators, previous) = match watchers {
+        Some(watchers) => watchers,
+        None => return ::iup_sys::IUP_DEFAULT,
+    };
+
+    let text_box = unsafe { TextBox::from_ptr(element) };
+
+    for validator in validators {
+        validator.touch(text_box);
+        validator.refresh();
+    }
+
+    previous.map_or(::iup_sys::IUP_DEFAULT, |previous| previous(element))
+}
+
+struct Field {
+    name: String,
+    text_box: TextBox,
+    rules: Vec<Rule>,
+    error_label: Option<Label>,
+    /// Errors are only shown once the field has been edited or `validate()` has been called.
+    touched: bool,
+    original_bg: Option<Color>,
+    original_tip: Option<String>,
+    /// If the error color or an error tooltip is currently shown, so it can be reverted.
+    showing_color: Cell<bool>,
+    showing_tip: Cell<bool>,
+}
+
+struct ValidatorState {
+    fields: Vec<Field>,
+    form_rules: Vec<(TextBox, FormCheckFn)>,
+    submit_buttons: Vec<Button>,
+    error_color: Option<Color>,
+    error_tooltips: bool,
+}
+
+/// A set of fields and the rules they must pass, which checks them as the user types.
+///
+/// Invalid fields are shown with a red background and their errors as tooltips, by default.
+/// Errors are shown once a field has been edited, or for all fields after `validate()`.
+///
+/// Cloning a `Validator` gives another handle to the same form.
+///
+/// ##Note
+/// Each field's `OnValueChange` callback is used for validation. A callback set before the field
+/// is added is still invoked, after validation, but one set afterwards replaces validation.
+#[derive(Clone)]
+pub struct Validator(Rc<RefCell<ValidatorState>>);
+
+impl Default for Validator {
+    fn default() -> Validator {
+        Validator::new()
+    }
+}
+
+impl Validator {
+    pub fn new() -> Validator {
+        Validator(Rc::new(RefCell::new(ValidatorState {
+            fields: Vec::new(),
+            form_rules: Vec::new(),
+            submit_buttons: Vec::new(),
+            error_color: Some(Color::rgb(255, 200, 200)),
+            error_tooltips: true,
+        })))
+    }
+
+    /// Add `text_box` as a field with the given `name`, shown in error reports, which must pass
+    /// all of `rules`.
+    pub fn field<N: Into<String>>(self, name: N, text_box: TextBox, rules: Vec<Rule>) -> Self {
+        self.0.borrow_mut().fields.push(Field {
+            name: name.into(),
+            text_box,
+            rules,
+            error_label: None,
+            touched: false,
+            original_bg: text_box.get_bg_color(),
+            original_tip: text_box.get_tooltip().map(|tip| tip.to_string()),
+            showing_color: Cell::new(false),
+            showing_tip: Cell::new(false),
+        });
+
+        watch_field(text_box, self.clone());
+
+        self.refresh();
+        self
+    }
+
+    /// Add a rule involving more than one field, e.g. that two passwords match. Its error is
+    /// shown on `text_box`, which must already be a field.
+    ///
+    /// The rule is checked when any field changes.
+    pub fn form_rule<F>(self, text_box: TextBox, check: F) -> Self
+    where F: Fn() -> Result<(), String> + 'static {
+        self.0.borrow_mut().form_rules.push((text_box, Rc::new(check)));
+        self.refresh();
+        self
+    }
+
+    /// Show the first error for `text_box` in `label`, which is cleared while it is valid.
+    pub fn show_errors_in(self, text_box: TextBox, label: Label) -> Self {
+        if let Some(field) = self.0.borrow_mut().fields.iter_mut()
+            .find(|field| field.text_box.ptr() == text_box.ptr()) {
+            field.error_label = Some(label);
+        }
+
+        self.refresh();
+        self
+    }
+
+    /// Set the background color of invalid fields, or `None` to leave it unchanged. Fields
+    /// shown in the previous color get their original color back.
+    pub fn set_error_color(self, color: Option<Color>) -> Self {
+        self.0.borrow_mut().error_color = color;
+        self.refresh();
+        self
+    }
+
+    /// Set if the errors of invalid fields should be shown as their tooltips. If not, fields
+    /// showing an error get their original tooltip back.
+    pub fn set_error_tooltips(self, error_tooltips: bool) -> Self {
+        self.0.borrow_mut().error_tooltips = error_tooltips;
+        self.refresh();
+        self
+    }
+
+    /// Enable `button` only while every field is valid, e.g. the button that submits the form.
+    pub fn bind_submit(self, button: Button) -> Self {
+        self.0.borrow_mut().submit_buttons.push(button);
+        self.refresh();
+        self
+    }
+
+    /// Check every field without changing how they are shown.
+    pub fn report(&self) -> ValidationReport {
+        let state = self.0.borrow();
+        let mut report = ValidationReport::default();
+
+        for field in &state.fields {
+            let text = field.text_box.get_text().to_string();
+
+            for rule in &field.rules {
+                if let Err(message) = rule.check(&text) {
+                    report.errors.push(ValidationError {
+                        field: field.name.clone(),
+                        text_box: field.text_box,
+                        message,
+                    });
+                }
+            }
+        }
+
+        for &(text_box, ref check) in &state.form_rules {
+            if let Err(message) = check() {
+                let field = state.fields.iter()
+                    .find(|field| field.text_box.ptr() == text_box.ptr())
+                    .map_or_else(String::new, |field| field.name.clone());
+
+                report.errors.push(ValidationError { field, text_box, message });
+            }
+        }
+
+        report
+    }
+
+    /// Check every field and show the errors on all of them, including fields which have not
+    /// been edited yet, e.g. when the user tries to submit the form.
+    pub fn validate(&self) -> ValidationReport {
+        for field in &mut self.0.borrow_mut().fields {
+            field.touched = true;
+        }
+
+        self.refresh()
+    }
+
+    fn touch(&self, text_box: TextBox) {
+        for field in &mut self.0.borrow_mut().fields {
+            if field.text_box.ptr() == text_box.ptr() {
+                field.touched = true;
+            }
+        }
+    }
+
+    fn refresh(&self) -> ValidationReport {
+        let report = self.report();
+        let state = self.0.borrow();
+
+        for field in &state.fields {
+            let error = report.errors_for(field.text_box).first()
+                .filter(|_| field.touched)
+                .map(|error| error.message.clone());
+
+            show_error(&state, field, error);
+        }
+
+        for &button in &state.submit_buttons {
+            button.set_enabled(report.is_valid());
+        }
+
+        report
+    }
+}
+
+fn show_error(state: &ValidatorState, field: &Field, error: Option<String>) {
+    let text_box = field.text_box;
+
+    let color = error.as_ref().and(state.error_color);
+
+    // The original color is only restored if it was replaced, so it can be changed elsewhere
+    // while the field is valid.
+    if color.is_some() || field.showing_color.get() {
+        match (color, field.original_bg) {
+            (Some(color), _) => { text_box.set_bg_color(color); },
+            (None, Some(original)) => { text_box.set_bg_color(original); },
+            (None, None) => text_box.set_opt_str_attribute::<String>(::attrs::BG_COLOR, None),
+        }
+
+        field.showing_color.set(color.is_some());
+    }
+
+    let tip = error.clone().filter(|_| state.error_tooltips);
+
+    if tip.is_some() || field.showing_tip.get() {
+        field.showing_tip.set(tip.is_some());
+        text_box.set_opt_str_attribute(::attrs::TIP, tip.or_else(|| field.original_tip.clone()));
+    }
+
+    if let Some(label) = field.error_label {
+        label.set_text(error.as_ref().map_or("", |error| &error[..]));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rule;
+
+    fn passes(rule: &Rule, text: &str) -> bool {
+        rule.check(text).is_ok()
+    }
+
+    #[test]
+    fn required() {
+        let rule = Rule::required();
+        assert!(passes(&rule, "a"));
+        assert_eq!(rule.check(" \t"), Err("This field is required".into()));
+        assert!(!passes(&rule, ""));
+    }
+
+    #[test]
+    fn empty_text_skips_other_rules() {
+        assert!(passes(&Rule::min_length(3), ""));
+        assert!(passes(&Rule::min(1), ""));
+        assert!(passes(&Rule::email(), ""));
+        assert!(passes(&Rule::custom(|_| Err("always".into())), ""));
+    }
+
+    #[test]
+    fn lengths_count_characters() {
+        assert!(passes(&Rule::min_length(3), "\u{e9}t\u{e9}"));
+        let error = Err("Must be at least 4 characters".into());
+        assert_eq!(Rule::min_length(4).check("\u{e9}t\u{e9}"), error);
+        assert!(passes(&Rule::max_length(3), "\u{e9}t\u{e9}"));
+        assert!(!passes(&Rule::max_length(2), "\u{e9}t\u{e9}"));
+    }
+
+    #[test]
+    fn numbers() {
+        assert!(passes(&Rule::min(0), " 0 "));
+        assert_eq!(Rule::min(0).check("-1"), Err("Must be at least 0".into()));
+        assert_eq!(Rule::max(1.5).check("2"), Err("Must be at most 1.5".into()));
+        assert_eq!(Rule::max(10u8).check("ten"), Err("Must be a number".into()));
+        assert_eq!(Rule::min(0u8).check("-1"), Err("Must be a number".into()));
+    }
+
+    #[test]
+    fn email() {
+        let rule = Rule::email();
+
+        for valid in &["a@b.co", " name.last@mail.example.org "] {
+            assert!(passes(&rule, valid), "{} should be valid", valid);
+        }
+
+        for invalid in &["a", "@b.co", "a@b", "a@b.", "a@.co", "a@b@c.co", "a b@c.co"] {
+            assert!(!passes(&rule, invalid), "{} should be invalid", invalid);
+        }
+    }
+
+    #[test]
+    fn pattern() {
+        let rule = Rule::pattern("Must be digits", |text|
+            text.chars().all(|c| c.is_ascii_digit())
+        );
+        assert!(passes(&rule, "123"));
+        assert_eq!(rule.check("12a"), Err("Must be digits".into()));
+    }
+}
@@ -3,7 +3,11 @@
 use utils::cstr::AsCStr;
 
 use base::{BaseWidget, Downcast};
+use color::Color;
+use container::{size_str, Expand};
 use dialog::Dialog;
+use font::{Font, TextMetrics};
+use tooltip::Tooltip;
 use widget_prelude::IUPPtr;
 
 use ::KISSContext;
@@ -133,6 +137,175 @@ pub trait Widget: IUPWidget {
         (width as u32, height as u32)
     }
 
+    /// Set the font this widget renders its text with.
+    ///
+    /// Does nothing if the widget does not render text.
+    fn set_font(self, font: &Font) -> Self {
+        self.set_str_attribute(::attrs::FONT, font.to_string());
+        self
+    }
+
+    /// Get the font this widget renders its text with.
+    ///
+    /// Returns `None` if the widget does not render text.
+    fn get_font(&self) -> Option<Font> {
+        self.get_str_attribute(::attrs::FONT).and_then(|font| font.parse().ok())
+    }
+
+    /// Get the measurements of this widget's current font, for sizing it to its content.
+    fn get_text_metrics(self) -> TextMetrics {
+        TextMetrics::of(self)
+    }
+
+    /// Measure the size of `text` in pixels when rendered in this widget's current font, as
+    /// `(width, height)`.
+    fn get_text_size(self, text: &str) -> (u32, u32) {
+        ::font::text_size(self, text)
+    }
+
+    /// Set the color this widget renders its text and foreground elements with.
+    ///
+    /// Does nothing if the widget does not support a foreground color.
+    fn set_fg_color<C: Into<Color>>(self, color: C) -> Self {
+        self.set_str_attribute(::attrs::FG_COLOR, color.into().to_string());
+        self
+    }
+
+    /// Get the foreground color of this widget, if it has one.
+    fn get_fg_color(&self) -> Option<Color> {
+        self.get_str_attribute(::attrs::FG_COLOR).and_then(|color| color.parse().ok())
+    }
+
+    /// Set the color this widget fills its background with.
+    ///
+    /// Does nothing if the widget does not support a background color.
+    /// To fill an area containing several widgets, see `container::BackgroundBox`.
+    fn set_bg_color<C: Into<Color>>(self, color: C) -> Self {
+        self.set_str_attribute(::attrs::BG_COLOR, color.into().to_string());
+        self
+    }
+
+    /// Get the background color of this widget, if it has one.
+    fn get_bg_color(&self) -> Option<Color> {
+        self.get_str_attribute(::attrs::BG_COLOR).and_then(|color| color.parse().ok())
+    }
+
+    /// Set the tooltip shown when the mouse hovers over this widget.
+    ///
+    /// Accepts either a string or a `tooltip::Tooltip` for more options.
+    ///
+    /// ##Panics
+    /// If any `WidgetStr` instances from `self.get_tooltip()` are still reachable.
+    fn set_tooltip<T: Into<Tooltip>>(self, tooltip: T) -> Self {
+        tooltip.into().apply(self);
+        self
+    }
+
+    /// Get the text of this widget's tooltip, if one is set.
+    fn get_tooltip(&self) -> Option<WidgetStr<'_>> {
+        self.get_str_attribute(::attrs::TIP)
+    }
+
+    /// Show or hide this widget's tooltip immediately, without waiting for the mouse to hover.
+    fn set_tooltip_visible(self, visible: bool) -> Self {
+        self.set_bool_attribute(::attrs::TIP_VISIBLE, visible);
+        self
+    }
+
+    /// Set how this widget should grow to fill the space available in its container.
+    fn set_expand(self, expand: Expand) -> Self {
+        self.set_const_str_attribute(::attrs::EXPAND, expand.as_cstr());
+        self
+    }
+
+    /// Set the size of this widget in character units: `width` in quarters of the average
+    /// character width, and `height` in eighths of the character height of the current font.
+    ///
+    /// Like `set_user_size()`, this is the size the widget will request from its container.
+    /// Using character units keeps the layout proportional when the font changes.
+    fn set_size(self, width: u32, height: u32) -> Self {
+        self.set_str_attribute(::attrs::SIZE, size_str(width, height));
+        self
+    }
+
+    /// Set the size in pixels this widget will request from its container, overriding its
+    /// natural size. A dimension set to `None` will keep its natural size.
+    ///
+    /// The widget may still be made larger by `set_expand()`.
+    fn set_user_size(self, width: Option<u32>, height: Option<u32>) -> Self {
+        fn dim(dim: Option<u32>) -> String {
+            dim.map_or_else(String::new, |dim| dim.to_string())
+        }
+
+        self.set_str_attribute(::attrs::RASTERSIZE, format!("{}x{}", dim(width), dim(height)));
+        self
+    }
+
+    /// Get the size in pixels this widget requests from its container, as set by
+    /// `set_user_size()` or `set_size()`.
+    ///
+    /// Returns `None` if no size was set. A dimension which keeps its natural size is `0`.
+    fn get_user_size(self) -> Option<(u32, u32)> {
+        self.get_opt_int2_attribute(::attrs::USERSIZE)
+            .map(|(width, height)| (width as u32, height as u32))
+    }
+
+    /// Set the minimum size of this widget in pixels, which the layout will not shrink it below.
+    fn set_min_size(self, width: u32, height: u32) -> Self {
+        self.set_str_attribute(::attrs::MINSIZE, size_str(width, height));
+        self
+    }
+
+    /// Set the maximum size of this widget in pixels, which the layout will not expand it past.
+    fn set_max_size(self, width: u32, height: u32) -> Self {
+        self.set_str_attribute(::attrs::MAXSIZE, size_str(width, height));
+        self
+    }
+
+    /// Give this widget the keyboard focus, so it receives keyboard input.
+    ///
+    /// Does nothing if the widget cannot be focused, or is not visible and enabled.
+    fn set_focus(self) -> Self {
+        unsafe { iup_sys::IupSetFocus(self.ptr()); }
+        self
+    }
+
+    /// Returns `true` if this widget currently has the keyboard focus.
+    fn has_focus(self) -> bool {
+        unsafe { iup_sys::IupGetFocus() == self.ptr() }
+    }
+
+    /// Set if this widget can receive the keyboard focus, either by the user clicking it or
+    /// navigating to it with the Tab key.
+    ///
+    /// Does nothing if the widget can never be focused.
+    fn set_can_focus(self, can_focus: bool) -> Self {
+        self.set_bool_attribute(::attrs::CANFOCUS, can_focus);
+        self
+    }
+
+    /// Move the keyboard focus to the next widget in the dialog that can receive it, as if the
+    /// user pressed Tab while this widget had focus.
+    ///
+    /// Returns the widget which received the focus, if any.
+    fn focus_next(self) -> Option<BaseWidget> {
+        unsafe {
+            let ptr = iup_sys::IupNextField(self.ptr());
+            BaseWidget::from_ptr_opt(ptr)
+        }
+    }
+
+    /// Move the keyboard focus to the previous widget in the dialog that can receive it, as if
+    /// the user pressed Shift+Tab while this widget had focus.
+    ///
+    /// Returns the widget which received the focus, if any.
+    fn focus_previous(self) -> Option<BaseWidget> {
+        unsafe {
+            let ptr = iup_sys::IupPreviousField(self.ptr());
+            BaseWidget::from_ptr_opt(ptr)
+        }
+    }
+
     /// Store this widget under `name`, returning the previous widget stored, if any.
     ///
     /// It may later be retrieved from any valid KISS-UI context 
@@ -146,6 +319,19 @@ pub trait Widget: IUPWidget {
     }
 }
 
+/// Get the widget which currently has the keyboard focus, if any.
+///
+/// ##Panics
+/// If called outside a valid KISS-UI context.
+pub fn focused_widget() -> Option<BaseWidget> {
+    assert_kiss_running!();
+
+    unsafe {
+        let ptr = iup_sys::IupGetFocus();
+        BaseWidget::from_ptr_opt(ptr)
+    }
+}
+
 pub trait Destroy: Widget {
     fn destroy(self) {
         unsafe { 
@@ -270,6 +456,27 @@ pub trait IUPWidget: Copy {
         }
     }
 
+    fn set_opt_str_attribute_id<V>(self, name: &'static str, id: i32, val: Option<V>)
+    where V: Into<String> {
+        let c_val = val.map(V::into).map(CString::new).map(Result::unwrap);
+        unsafe {
+            iup_sys::IupSetStrAttributeId(
+                self.ptr(),
+                name.as_cstr(),
+                id,
+                c_val.as_ref().map_or_else(ptr::null, |c_val| c_val.as_ptr())
+            )
+        }
+    }
+
+    // Attributes with IDs are not tracked by `WidgetStr`, so the value is copied out.
+    fn get_str_attribute_id(&self, name: &'static str, id: i32) -> Option<String> {
+        unsafe {
+            let ptr = iup_sys::IupGetAttributeId(self.ptr(), name.as_cstr(), id);
+            ptr.as_ref().map(|_| CStr::from_ptr(ptr).to_string_lossy().into_owned())
+        }
+    }
+
     fn set_const_str_attribute(self, name: &'static str, val: &'static str) {
         KISSContext::assert_str_not_borrowed(self.ptr(), name);        
 
@@ -312,6 +519,18 @@ pub trait IUPWidget: Copy {
         (left, right)
     }
 
+    /// Like `get_int2_attribute()`, but returns `None` if the attribute is not set.
+    fn get_opt_int2_attribute(self, name: &'static str) -> Option<(i32, i32)> {
+        let mut left = 0;
+        let mut right = 0;
+
+        let count = unsafe {
+            iup_sys::IupGetIntInt(self.ptr(), name.as_cstr(), &mut left, &mut right)
+        };
+
+        if count != 0 { Some((left, right)) } else { None }
+    }
+
     fn set_float_attribute(self, name: &'static str, val: f32) {
         unsafe { iup_sys::IupSetFloat(self.ptr(), name.as_cstr(), val); } 
     }
@@ -320,6 +539,14 @@ pub trait IUPWidget: Copy {
         unsafe { iup_sys::IupGetFloat(self.ptr(), name.as_cstr()) }
     }
 
+    fn set_double_attribute(self, name: &'static str, val: f64) {
+        unsafe { iup_sys::IupSetDouble(self.ptr(), name.as_cstr(), val); }
+    }
+
+    fn get_double_attribute(self, name: &'static str) -> f64 {
+        unsafe { iup_sys::IupGetDouble(self.ptr(), name.as_cstr()) }
+    }
+
     fn set_bool_attribute(self, name: &'static str, val: bool) {
         let val = ::attrs::values::bool_yes_no(val);
         self.set_const_str_attribute(name, val);        
@@ -339,6 +566,17 @@ pub trait IUPWidget: Copy {
     fn set_callback(self, name: &'static str, callback: ::iup_sys::Icallback) {
         unsafe { iup_sys::IupSetCallback(self.ptr(), name.as_cstr(), callback); } 
     }    
+
+    fn get_callback(self, name: &'static str) -> Option<::iup_sys::Icallback> {
+        extern "C" {
+            // `iup-sys` declares the return type as `Icallback`, which cannot be null.
+            fn IupGetCallback(
+                ih: *mut iup_sys::Ihandle, name: *const ::libc::c_char
+            ) -> Option<iup_sys::Icallback>;
+        }
+
+        unsafe { IupGetCallback(self.ptr(), name.as_cstr()) }
+    }
 }
 
 impl<'a, T: IUPWidget> IUPWidget for &'a T {
@@ -372,6 +610,36 @@ macro_rules! impl_widget {
         }
     };
 
+    ($ty:ident<$($param:ident),+>) => {
+        impl<$($param),+> ::widget::IUPWidget for $ty<$($param),+> {
+            unsafe fn from_ptr(ptr: ::widget_prelude::IUPPtr) -> Self {
+                assert!(
+                    !ptr.is_null(),
+                    concat!(
+                        concat!("Failed to construct ", stringify!($ty)),
+                        "; pointer returned from IUP was null!"
+                    )
+                );
+
+                $ty(ptr, ::std::marker::PhantomData)
+            }
+
+            fn ptr(self) -> ::widget_prelude::IUPPtr {
+                self.0
+            }
+        }
+
+        impl<$($param),+> ::widget::Widget for $ty<$($param),+> {}
+
+        impl<$($param),+> Copy for $ty<$($param),+> {}
+
+        impl<$($param),+> Clone for $ty<$($param),+> {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+    };
+
     ($ty:ident) => {
         impl ::widget::IUPWidget for $ty {
             unsafe fn from_ptr(ptr: ::widget_prelude::IUPPtr) -> Self {